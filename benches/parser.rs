@@ -10,7 +10,7 @@ fn bench_single_instance(b: Bencher) {
     let input = "This is some text. {{ variable }} and {{ toLower(trim(variable)) }}";
 
     let ctx = SrTemplate::default();
-    ctx.add_variable("variable", &"Variable");
+    ctx.add_variable("variable", "Variable");
 
     b.bench(|| {
         ctx.render(input).unwrap();
@@ -20,7 +20,7 @@ fn bench_single_instance(b: Bencher) {
 #[divan::bench(args = ["This is some text. {{ variable }} and {{ toLower(trim(variable)) }}"])]
 fn bench_iter_instance(input: &str) {
     let ctx = SrTemplate::default();
-    ctx.add_variable("variable", &"Variable");
+    ctx.add_variable("variable", "Variable");
     ctx.render(input).unwrap();
 }
 
@@ -29,7 +29,7 @@ fn bench_clone_instance(b: Bencher) {
     let input = "This is some text. {{ variable }} and {{ toLower(trim(variable)) }}";
 
     let ctx = SrTemplate::default();
-    ctx.add_variable("variable", &"Variable");
+    ctx.add_variable("variable", "Variable");
 
     b.with_inputs(|| ctx.clone())
         .bench_values(|ctx| ctx.render(input).unwrap())
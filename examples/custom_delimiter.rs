@@ -2,7 +2,7 @@ use srtemplate::SrTemplate;
 
 fn main() {
     let mut ctx = SrTemplate::default();
-    ctx.add_variable("var", &"mUnDo");
+    ctx.add_variable("var", "mUnDo");
 
     let template = "Hola {{ toLower(var) }}";
 
@@ -16,7 +16,7 @@ fn main() {
 
     // Creating new instance of template like shell syntax
     let ctx = SrTemplate::with_delimiter("${", "}");
-    ctx.add_variable("var", &"mUnDo");
+    ctx.add_variable("var", "mUnDo");
     let template = "Hola ${ toLower(var)}";
 
     println!("Rendered: {}", ctx.render(template).unwrap());
@@ -18,7 +18,7 @@ fn to_title(args: &[String]) -> FuncResult {
 
 fn main() {
     let ctx = SrTemplate::default();
-    ctx.add_variable("var", &"mUnDo");
+    ctx.add_variable("var", "mUnDo");
     ctx.add_function("toTitle", to_title);
 
     let template = "Hola {{ toTitle(var) }}";
@@ -9,11 +9,17 @@ fn main() {
         Ok(result) => println!("Rendered: {result}"),
         Err(e) => match e {
             srtemplate::Error::BadSyntax(e) => println!("Invalid syntaxis: {e}"),
-            srtemplate::Error::VariableNotFound(e) => println!("Variable not found: {e}"),
-            srtemplate::Error::FunctionNotImplemented(e) => {
-                println!("Function not supported: {e}")
+            srtemplate::Error::VariableNotFound(e, at) => {
+                println!("Variable not found: {e} ({at})")
+            }
+            srtemplate::Error::FunctionNotImplemented(e, at) => {
+                println!("Function not supported: {e} ({at})")
+            }
+            srtemplate::Error::Function(e, at) => println!("Error procesing function: {e} ({at})"),
+            srtemplate::Error::Serialize(e) => println!("Error serializing variables: {e}"),
+            e @ (srtemplate::Error::LoadVariables { .. } | srtemplate::Error::Write(_)) => {
+                println!("{e}")
             }
-            srtemplate::Error::Function(e) => println!("Error procesing function: {e}"),
         },
     }
 }
@@ -1,21 +1,44 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
-use srtemplate::{function, SrTemplate, Variable};
+use srtemplate::{
+    compile_template, function, include_template, template, SrTemplate, Template, Variable,
+};
 
 #[function]
 fn merge(name: String, age: u8) {
     Ok(format!("{name}_{age}"))
 }
 
+// rendered by plain Rust code, without parsing the template at runtime
+compile_template!(fn merged, "Hola {{ merge(var, other) }} y {{ toUpper(var) }}");
+
 #[derive(Variable)]
 #[template(case_fields = "pascal")]
 pub struct User {
     name: String,
     last_name: String,
+    #[allow(dead_code)]
     #[template(ignore)]
     age: u8,
     #[template(rename = "correo")]
     email: String,
+    #[template(nested)]
+    address: Address,
+    #[template(flatten)]
+    contact: Contact,
+}
+
+#[derive(Variable)]
+pub struct Address {
+    city: String,
+    country: String,
+}
+
+#[derive(Variable)]
+#[template(case_fields = "pascal")]
+pub struct Contact {
+    phone: String,
 }
 
 #[derive(Variable)]
@@ -31,6 +54,46 @@ impl Display for TestUnit {
 #[derive(Variable)]
 pub struct TestTuple(u8, u8);
 
+#[derive(Variable)]
+pub struct Order {
+    id: u32,
+    coupon: Option<String>,
+    #[template(default = "standard")]
+    shipping: Option<String>,
+    items: Vec<String>,
+    prices: BTreeMap<String, f32>,
+    #[template(with = "format_total")]
+    total: f32,
+}
+
+fn format_total(total: &f32) -> String {
+    format!("${total:.2}")
+}
+
+#[derive(Variable)]
+#[template(prefix = false)]
+pub struct Settings {
+    lang: String,
+}
+
+#[derive(Variable)]
+pub enum Status {
+    Online,
+    #[template(rename = "away")]
+    Idle {
+        minutes: u32,
+    },
+}
+
+#[derive(Template)]
+#[template(source = "{{ toUpper(user) }} has {{ unread }} new messages from {{ sender }}")]
+pub struct Inbox<'a, T> {
+    user: &'a str,
+    #[template(rename = "sender")]
+    from: T,
+    unread: usize,
+}
+
 impl Default for User {
     fn default() -> Self {
         Self {
@@ -38,22 +101,70 @@ impl Default for User {
             last_name: "Ribera".into(),
             email: "sergioribera@rustlang-es.org".into(),
             age: 22,
+            address: Address {
+                city: "Santa Cruz".into(),
+                country: "Bolivia".into(),
+            },
+            contact: Contact {
+                phone: "+591 700000".into(),
+            },
         }
     }
 }
 
 fn main() {
     let ctx = SrTemplate::default();
-    ctx.add_variable("var", &"mUnDo");
-    ctx.add_variable("other", &255u8);
+    ctx.add_variable("var", "mUnDo");
+    ctx.add_variable("other", 255u8);
 
     ctx.add_function("merge", merge);
 
-    ctx.add(&User::default());
+    ctx.add(User::default());
     ctx.add(&TestUnit);
-    ctx.add(&TestTuple(5, 12));
+    ctx.add(TestTuple(5, 12));
 
-    let template = "Hola {{ merge(var, other) }}, {{ User.Name }} {{ User.LastName}} @ {{ User.Correo }}. {{ testunit }} {{ TestTuple.0 }} {{ TestTuple.1 }}";
+    ctx.add(Status::Idle { minutes: 5 });
+    ctx.add(Settings { lang: "es".into() });
+    ctx.add_as("sender", User::default());
+    ctx.add_flat(Address {
+        city: "La Paz".into(),
+        country: "Bolivia".into(),
+    });
+    ctx.add(Order {
+        id: 7,
+        coupon: None,
+        shipping: None,
+        items: vec!["apple".into(), "pear".into()],
+        prices: BTreeMap::from([("apple".into(), 1.5), ("pear".into(), 2.0)]),
+        total: 3.5,
+    });
+
+    let template = "Hola {{ merge(var, other) }}, {{ User.Name }} {{ User.LastName}} @ {{ User.Correo }} ({{ User.Address.city }}, {{ User.Phone }}). {{ testunit }} {{ TestTuple.0 }} {{ TestTuple.1 }} {{ Status }} {{ Status.minutes }}";
 
     println!("Rendered: {}", ctx.render(template).unwrap());
+
+    // parsed and checked at compile time
+    let template = template!("Hola {{ toLower(var) }} <% User.Name %>", "<%", "%>");
+    println!("Rendered: {}", ctx.render_parsed(&template).unwrap());
+
+    let template = include_template!("examples/templates/greeting.txt");
+    print!("Rendered: {}", ctx.render_parsed(&template).unwrap());
+
+    let inbox = Inbox {
+        user: "Sergio",
+        from: TestUnit,
+        unread: 3,
+    };
+    println!("Rendered: {}", inbox.render(&ctx).unwrap());
+    println!("Rendered: {inbox}");
+
+    let template = "Order #{{ Order.id }} ({{ Order.shipping }}): {{ Order.items.0 }} x {{ Order.prices.apple }}, {{ Order.items.1 }} x {{ Order.prices.pear }} = {{ Order.total }}";
+    println!("Rendered: {}", ctx.render(template).unwrap());
+
+    let template = "[{{ lang }}] {{ sender.Name }} from {{ city }}";
+    println!("Rendered: {}", ctx.render(template).unwrap());
+
+    let mut out = String::new();
+    merged(&ctx, &mut out).unwrap();
+    println!("Rendered: {out}");
 }
@@ -15,8 +15,8 @@ fn merge(args: &[String]) -> FuncResult {
 
 fn main() {
     let ctx = SrTemplate::default();
-    ctx.add_variable("var", &"mUnDo");
-    ctx.add_variable("other", &255u8);
+    ctx.add_variable("var", "mUnDo");
+    ctx.add_variable("other", 255u8);
 
     ctx.add_function("merge", merge);
 
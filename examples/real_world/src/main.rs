@@ -192,7 +192,7 @@ fn App() -> impl IntoView {
                                 </div>
                             }.into_view()
                         }
-                        Some(Error::VariableNotFound(var)) => {
+                        Some(Error::VariableNotFound(var, _)) => {
                             view! {
                                 <div class="bg-yellow-100 border border-yellow-400 text-yellow-800 rounded-lg p-4">
                                     <div class="flex items-center">
@@ -204,7 +204,7 @@ fn App() -> impl IntoView {
                                 </div>
                             }.into_view()
                         }
-                        Some(Error::FunctionNotImplemented(func)) => {
+                        Some(Error::FunctionNotImplemented(func, _)) => {
                             view! {
                                 <div class="bg-blue-100 border border-blue-400 text-blue-800 rounded-lg p-4">
                                     <div class="flex items-center">
@@ -216,7 +216,7 @@ fn App() -> impl IntoView {
                                 </div>
                             }.into_view()
                         }
-                        Some(Error::Function(err)) => {
+                        Some(Error::Function(err, _)) => {
                             view! {
                                 <div class="bg-purple-100 border border-purple-400 text-purple-800 rounded-lg p-4">
                                     <div class="flex items-center">
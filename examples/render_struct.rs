@@ -6,9 +6,9 @@ struct Point {
     y: f32,
 }
 
-impl ToString for Point {
-    fn to_string(&self) -> String {
-        format!("(x: {}, y: {})", self.x, self.y)
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(x: {}, y: {})", self.x, self.y)
     }
 }
 
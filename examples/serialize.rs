@@ -0,0 +1,33 @@
+use serde::Serialize;
+use srtemplate::SrTemplate;
+
+#[derive(Serialize)]
+struct Item {
+    name: String,
+    price: f32,
+}
+
+#[derive(Serialize)]
+struct Order {
+    id: u32,
+    customer: Option<String>,
+    items: Vec<Item>,
+}
+
+fn main() {
+    let ctx = SrTemplate::default();
+
+    let order = Order {
+        id: 1024,
+        customer: Some("Sergio".into()),
+        items: vec![Item {
+            name: "Rust Book".into(),
+            price: 39.99,
+        }],
+    };
+
+    ctx.add_serialize("order", &order).unwrap();
+
+    let template = "Order #{{ order.id }} for {{ order.customer }}: {{ order.items.0.name }} ({{ order.items.0.price }})";
+    println!("Rendered: {}", ctx.render(template).unwrap());
+}
@@ -0,0 +1,225 @@
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote;
+use venial::Error;
+
+use crate::template::{parse_arguments, usize_literal, Source, Template};
+use srtemplate_parser::TemplateNode;
+
+const MATH_OPERATIONS: [&str; 4] = ["add", "sub", "mul", "div"];
+const MATH_TYPES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+];
+
+pub fn gen_compiled(input: TokenStream) -> Result<TokenStream, Error> {
+    let mut tokens = input.into_iter().peekable();
+
+    // attributes and visibility of the function, everything before `fn`
+    let mut signature = TokenStream::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident == "fn" => break,
+            Some(token) => signature.extend([token]),
+            None => return Err(Error::new("expected `fn name, \"template\"`")),
+        }
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name,
+        token => {
+            let span = token.map_or_else(Span::call_site, |t| t.span());
+            return Err(Error::new_at_span(
+                span,
+                "expected the name of the function",
+            ));
+        }
+    };
+    match tokens.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+        _ => {
+            return Err(Error::new_at_span(
+                name.span(),
+                "expected `,` after the name",
+            ))
+        }
+    }
+
+    let source = match tokens.peek() {
+        Some(TokenTree::Ident(ident)) if ident == "path" => {
+            tokens.next();
+            match tokens.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '=' => Source::File,
+                _ => return Err(Error::new_at_span(name.span(), "expected `path = \"...\"`")),
+            }
+        }
+        _ => Source::Inline,
+    };
+
+    let (args, spans) = parse_arguments(tokens.collect())?;
+    let (value, start, close) = match args.as_slice() {
+        [value] => (value.as_str(), "{{", "}}"),
+        [value, start, close] => (value.as_str(), start.as_str(), close.as_str()),
+        _ => {
+            return Err(Error::new(
+                "expected a template and, optionally, its start and close delimiters",
+            ))
+        }
+    };
+
+    let template = Template::load(value, spans[0], source)?;
+    let nodes = template.parse(start, close)?;
+    let source = &template.source;
+    let statements = nodes
+        .iter()
+        .map(|node| {
+            let value = match node {
+                TemplateNode::Variable(..) | TemplateNode::Function(..) => {
+                    let expr = expression(node)?;
+                    quote!(&#expr)
+                }
+                node => {
+                    let text = node.text();
+                    quote!(#text)
+                }
+            };
+            Ok(quote!(out.write_str(#value)?;))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {
+        #[allow(unused_variables)]
+        #signature fn #name(
+            vars: &impl srtemplate::Variables,
+            out: &mut impl std::fmt::Write,
+        ) -> Result<(), srtemplate::Error> {
+            #[allow(dead_code)]
+            const SOURCE: &str = #source;
+            #(#statements)*
+            Ok(())
+        }
+    })
+}
+
+/// Expression evaluating a node into a `&str`, a `String` or a `Cow<str>`
+fn expression(node: &TemplateNode) -> Result<TokenStream, Error> {
+    let span = node.span();
+    let (start, end) = (usize_literal(span.start), usize_literal(span.end));
+
+    Ok(match node {
+        TemplateNode::Variable(name, _) => {
+            quote!(srtemplate::compiled::variable(vars, #name, SOURCE, #start..#end)?)
+        }
+        TemplateNode::Function(name, args, _) => {
+            let function = function_path(name)?;
+            let args = args
+                .iter()
+                .map(|arg| {
+                    let arg = expression(arg)?;
+                    Ok(quote!(String::from(#arg)))
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            quote! {
+                srtemplate::compiled::function(
+                    #function(&[#(#args),*]),
+                    SOURCE,
+                    #start..#end,
+                )?
+            }
+        }
+        node => {
+            let text = node.text();
+            quote!(#text)
+        }
+    })
+}
+
+/// Path of the function called by the template, builtin functions are taken from
+/// `srtemplate::builtin` and any other function must be in scope with the same name
+fn function_path(name: &str) -> Result<TokenStream, Error> {
+    let ident = |name: &str| Ident::new(name, Span::call_site());
+
+    Ok(match name {
+        "env" => quote!(srtemplate::builtin::os::env),
+        "toLower" => quote!(srtemplate::builtin::text::to_lower),
+        "toUpper" => quote!(srtemplate::builtin::text::to_upper),
+        "trim" => quote!(srtemplate::builtin::text::trim),
+        name if is_math(name) => {
+            let name = ident(name);
+            quote!(srtemplate::builtin::math::#name)
+        }
+        name if is_identifier(name) => {
+            let name = ident(name);
+            quote!(#name)
+        }
+        name => {
+            return Err(Error::new(format!(
+                "`{name}` is not a valid identifier, it cannot be called from a compiled template"
+            )))
+        }
+    })
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn is_math(name: &str) -> bool {
+    let (operation, ty) = name.split_once('_').unwrap_or((name, "i32"));
+    MATH_OPERATIONS.contains(&operation) && MATH_TYPES.contains(&ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_paths() {
+        assert_eq!(
+            function_path("toLower").unwrap().to_string(),
+            quote!(srtemplate::builtin::text::to_lower).to_string()
+        );
+        assert_eq!(
+            function_path("add_f64").unwrap().to_string(),
+            quote!(srtemplate::builtin::math::add_f64).to_string()
+        );
+        assert_eq!(function_path("merge").unwrap().to_string(), "merge");
+        assert_eq!(function_path("add_str").unwrap().to_string(), "add_str");
+        assert!(function_path("a.b").is_err());
+    }
+
+    #[test]
+    fn expand_compiled() {
+        let tokens = gen_compiled(quote!(pub fn greet, "Hi {{ trim(name, \"x\") }}!")).unwrap();
+        let expected = quote! {
+            #[allow(unused_variables)]
+            pub fn greet(
+                vars: &impl srtemplate::Variables,
+                out: &mut impl std::fmt::Write,
+            ) -> Result<(), srtemplate::Error> {
+                #[allow(dead_code)]
+                const SOURCE: &str = "Hi {{ trim(name, \"x\") }}!";
+                out.write_str("Hi ")?;
+                out.write_str(&srtemplate::compiled::function(
+                    srtemplate::builtin::text::trim(&[
+                        String::from(srtemplate::compiled::variable(vars, "name", SOURCE, 11..15)?),
+                        String::from("x")
+                    ]),
+                    SOURCE,
+                    6..21,
+                )?)?;
+                out.write_str("!")?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(tokens.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(gen_compiled(quote!("Hi")).is_err());
+        assert!(gen_compiled(quote!(fn greet "Hi")).is_err());
+        assert!(gen_compiled(quote!(fn greet, path = "missing.txt")).is_err());
+        assert!(gen_compiled(quote!(fn greet, "{{ trim( }}")).is_err());
+    }
+}
@@ -1,11 +1,33 @@
 use std::ops::Not;
 
-use proc_macro2::{Ident, TokenStream};
-use quote::quote_spanned;
-use venial::{Error, Function as VenialFunc, TypeExpr};
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use venial::{Attribute, AttributeValue, Error, FnTypedParam, Function as VenialFunc, TypeExpr};
 
-pub fn gen_function(func: VenialFunc) -> Result<TokenStream, Error> {
+/// How a parameter takes its value from the arguments of the template
+enum ParamKind {
+    /// The argument at the position of the parameter, which must be given
+    Required,
+    /// `Option<T>`, `None` if the argument is not given
+    Optional(TokenStream),
+    /// `#[default = ...]`, the default value if the argument is not given
+    Default(TokenStream),
+    /// Trailing `Vec<T>`, the rest of the arguments parsed into `T`
+    Variadic(TokenStream),
+    /// Trailing `&[String]`, the rest of the arguments as they are
+    Rest,
+}
+
+struct Param<'a> {
+    name: &'a Ident,
+    tk_mut: Option<&'a Ident>,
+    ty: &'a TypeExpr,
+    kind: ParamKind,
+}
+
+pub fn gen_function(options: TokenStream, func: VenialFunc) -> Result<TokenStream, Error> {
     let func_name = &func.name;
+    let raw_args = raw_args(options)?;
 
     let vis = func.vis_marker.as_ref();
 
@@ -16,16 +38,26 @@ pub fn gen_function(func: VenialFunc) -> Result<TokenStream, Error> {
             venial::FnParam::Receiver(receiver) => {
                 Some(Err(Error::new_at_tokens(receiver, "self is not permitted")))
             }
-            venial::FnParam::Typed(venial::FnTypedParam { name, ty, .. }) => {
-                ty.tokens.is_empty().not().then_some(Ok((name, ty)))
-            }
+            venial::FnParam::Typed(param) => param.ty.tokens.is_empty().not().then(|| {
+                let kind = param_kind(param)?;
+                Ok(Param {
+                    name: &param.name,
+                    tk_mut: param.tk_mut.as_ref(),
+                    ty: &param.ty,
+                    kind,
+                })
+            }),
         })
         .collect::<Result<Vec<_>, _>>()?;
-    let total_params = params.len();
+    let (min_params, max_params) = arity(&params)?;
+    let max_len = max_params.map(
+        |max_params| quote!(srtemplate::prelude::validations::args_max_len(args, #max_params)?;),
+    );
+    let function = func_name.to_string();
     let decl = params
-        .into_iter()
+        .iter()
         .enumerate()
-        .map(|(idx, (param, ty))| parse_param(idx, param, ty))
+        .map(|(idx, param)| parse_param(&function, idx, param))
         .collect::<Vec<TokenStream>>();
 
     let func_body = func
@@ -34,20 +66,569 @@ pub fn gen_function(func: VenialFunc) -> Result<TokenStream, Error> {
         .ok_or_else(|| Error::new_at_span(func.span(), "Function should have body"))?;
     let func_body = quote_spanned! {func_body.span() => #func_body};
 
+    // the body is kept in a function with the original signature, so `return` and `?`
+    // work with its return type, which is converted into a `FuncResult` afterwards
+    let generic_params = &func.generic_params;
+    let where_clause = &func.where_clause;
+    let mut inner_params = params
+        .iter()
+        .map(|param| {
+            let Param {
+                name, tk_mut, ty, ..
+            } = param;
+            quote!(#tk_mut #name: #ty)
+        })
+        .collect::<Vec<_>>();
+    let mut names = params.iter().map(|param| param.name).collect::<Vec<_>>();
+    // with `#[function(args)]` the body also reads the arguments as they were given
+    let args_ident = Ident::new("args", proc_macro2::Span::call_site());
+    if raw_args {
+        if let Some(param) = params.iter().find(|param| *param.name == "args") {
+            return Err(Error::new_at_span(
+                param.name.span(),
+                "a parameter named `args` conflicts with `#[function(args)]`",
+            ));
+        }
+        inner_params.push(quote!(args: &[String]));
+        names.push(&args_ident);
+    }
+    let docs = func
+        .attributes
+        .iter()
+        .filter(|attr| {
+            attr.get_single_path_segment()
+                .is_some_and(|name| name == "doc")
+        })
+        .collect::<Vec<_>>();
+    let signature_name = format_ident!("{func_name}_signature");
+    let signature = signature(&function, &params, &docs);
+    let signature_doc = format!(
+        "Signature of [`{function}`], to register it with `SrTemplate::add_function_with_signature`"
+    );
+    let register_name = format_ident!("register_{func_name}");
+    let register_doc = format!(
+        "Adds [`{function}`] to the context as `{function}`, along with its signature so its calls are checked by `SrTemplate::validate`"
+    );
+    let (return_ty, result) = match &func.return_ty {
+        Some(ty) => (quote!(#ty), convert_result(&ty.tokens)),
+        None => (quote!(srtemplate::prelude::FuncResult), quote!(result)),
+    };
+
     Ok(quote_spanned! { func.span() =>
+        #(#docs)*
         #vis fn #func_name(args: &[String]) -> srtemplate::prelude::FuncResult {
-            srtemplate::prelude::validations::args_min_len(args, #total_params)?;
-            srtemplate::prelude::validations::args_max_len(args, #total_params)?;
+            fn #func_name #generic_params (#(#inner_params),*) -> #return_ty #where_clause #func_body
+
+            srtemplate::prelude::validations::args_min_len(args, #min_params)?;
+            #max_len
 
             #(#decl)*
 
-            #func_body
+            let result = #func_name(#(#names),*);
+            #result
+        }
+
+        #[doc = #signature_doc]
+        #[allow(dead_code)]
+        #vis fn #signature_name() -> srtemplate::prelude::FunctionSignature {
+            #signature
+        }
+
+        #[doc = #register_doc]
+        #[allow(dead_code)]
+        #vis fn #register_name(ctx: &srtemplate::SrTemplate<'_>) {
+            ctx.add_function_with_signature(#function, #func_name, #signature_name());
         }
     })
 }
 
-fn parse_param(idx: usize, param: &Ident, ty: &TypeExpr) -> TokenStream {
-    quote_spanned! {ty.span() =>
-        let #param = args[#idx].parse::<#ty>().map_err(|_| srtemplate::prelude::FromArgsError::ParseFailed(#idx))?;
+/// Options of the attribute, `#[function(args)]` passes the raw arguments to the body
+fn raw_args(options: TokenStream) -> Result<bool, Error> {
+    let mut options = options.into_iter();
+    match (options.next(), options.next()) {
+        (None, _) => Ok(false),
+        (Some(TokenTree::Ident(ident)), None) if ident == "args" => Ok(true),
+        (Some(token), _) => Err(Error::new_at_span(
+            token.span(),
+            "expected `args`, like `#[function(args)]`",
+        )),
+    }
+}
+
+/// Expression building the `FunctionSignature` of the function, described by its doc comments
+fn signature(function: &str, params: &[Param], docs: &[&Attribute]) -> TokenStream {
+    let params = params.iter().map(|param| {
+        let name = param.name.to_string();
+        let ty = &param.ty;
+        let (method, ty) = match &param.kind {
+            ParamKind::Required => (quote!(param), type_name(&quote!(#ty))),
+            ParamKind::Optional(inner) => (quote!(optional_param), type_name(inner)),
+            ParamKind::Default(_) => (quote!(optional_param), type_name(&quote!(#ty))),
+            ParamKind::Variadic(inner) => (quote!(variadic_param), type_name(inner)),
+            ParamKind::Rest => (quote!(variadic_param), "String".to_owned()),
+        };
+        quote!(.#method(#name, #ty))
+    });
+    // each `///` line is a `#[doc = " line"]` attribute
+    let lines = docs.iter().filter_map(|attr| match &attr.value {
+        AttributeValue::Equals(_, value) => Some(value.iter().cloned().collect::<TokenStream>()),
+        _ => None,
+    });
+    let description = docs.is_empty().not().then(|| {
+        quote! {
+            .description(
+                [#(#lines),*]
+                    .iter()
+                    .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .trim(),
+            )
+        }
+    });
+
+    quote! {
+        srtemplate::prelude::FunctionSignature::new(#function)
+            #(#params)*
+            #description
+    }
+}
+
+/// Expression converting the value returned by the function, `result`, into a `FuncResult`
+fn convert_result(ty: &[TokenTree]) -> TokenStream {
+    let is_function_error = |ty: &TokenStream| {
+        matches!(
+            ty.clone().into_iter().last(),
+            Some(TokenTree::Ident(ident)) if ident == "FunctionError"
+        )
+    };
+
+    if matches!(ty.last(), Some(TokenTree::Ident(ident)) if ident == "FuncResult") {
+        return quote!(result);
+    }
+    if let Some([_, error]) = generic_args(ty, "Result").as_deref() {
+        let error = if is_function_error(error) {
+            quote!(error)
+        } else {
+            quote!(srtemplate::prelude::FunctionError::RuntimeError(
+                error.to_string()
+            ))
+        };
+        return quote!(result.map(|value| value.to_string()).map_err(|error| #error));
+    }
+    if generic_args(ty, "Option").is_some_and(|args| args.len() == 1) {
+        return quote!(Ok(result
+            .map(|value| value.to_string())
+            .unwrap_or_default()));
+    }
+
+    quote!(Ok(std::string::ToString::to_string(&result)))
+}
+
+/// Minimum and maximum number of arguments, there is no maximum with a variadic parameter
+fn arity(params: &[Param]) -> Result<(usize, Option<usize>), Error> {
+    let mut min = 0;
+    let mut optional = None;
+
+    for (idx, param) in params.iter().enumerate() {
+        match param.kind {
+            ParamKind::Required => {
+                if let Some(optional) = optional {
+                    return Err(Error::new_at_span(
+                        param.name.span(),
+                        format!(
+                            "`{}` is required, but it comes after the optional parameter `{optional}`",
+                            param.name
+                        ),
+                    ));
+                }
+                min += 1;
+            }
+            ParamKind::Optional(_) | ParamKind::Default(_) => {
+                optional.get_or_insert(param.name);
+            }
+            ParamKind::Variadic(_) | ParamKind::Rest if idx + 1 < params.len() => {
+                return Err(Error::new_at_span(
+                    param.name.span(),
+                    format!(
+                        "`{}` collects the remaining arguments, so it must be the last parameter",
+                        param.name
+                    ),
+                ));
+            }
+            ParamKind::Variadic(_) | ParamKind::Rest => return Ok((min, None)),
+        }
+    }
+
+    Ok((min, Some(params.len())))
+}
+
+fn param_kind(param: &FnTypedParam) -> Result<ParamKind, Error> {
+    let ty = &param.ty.tokens;
+
+    for attr in &param.attributes {
+        match (attr.get_single_path_segment(), &attr.value) {
+            (Some(name), AttributeValue::Equals(_, value)) if name == "default" => {
+                return Ok(ParamKind::Default(value.iter().cloned().collect()));
+            }
+            (Some(name), _) if name == "default" => {
+                return Err(Error::new_at_tokens(
+                    attr,
+                    "expected a default value like `#[default = 10]`",
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    if let Some([inner]) = generic_args(ty, "Option").as_deref() {
+        return Ok(ParamKind::Optional(inner.clone()));
+    }
+    if let Some([inner]) = generic_args(ty, "Vec").as_deref() {
+        return Ok(ParamKind::Variadic(inner.clone()));
+    }
+    // `&[String]` or `&'a [String]`
+    let is_rest = match ty.as_slice() {
+        [TokenTree::Punct(and), .., TokenTree::Group(group)] if and.as_char() == '&' => {
+            group.delimiter() == Delimiter::Bracket
+                && group.stream().to_string() == "String"
+                && ty.len() <= 4
+        }
+        _ => false,
+    };
+    if is_rest {
+        return Ok(ParamKind::Rest);
+    }
+
+    Ok(ParamKind::Required)
+}
+
+/// The arguments of `ty` if it is the generic type `name`, like `u8` in `Option<u8>`
+fn generic_args(ty: &[TokenTree], name: &str) -> Option<Vec<TokenStream>> {
+    let start = ty.iter().position(|token| match token {
+        TokenTree::Punct(p) => p.as_char() == '<',
+        _ => false,
+    })?;
+    let is_name = match &ty[..start] {
+        [.., TokenTree::Ident(ident)] => ident == name,
+        _ => false,
+    };
+    let inner = match &ty[start + 1..] {
+        [inner @ .., TokenTree::Punct(p)] if is_name && p.as_char() == '>' => inner,
+        _ => return None,
+    };
+
+    let mut args = vec![TokenStream::new()];
+    let mut depth = 0;
+    for token in inner {
+        match token {
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' => depth -= 1,
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => {
+                args.push(TokenStream::new());
+                continue;
+            }
+            _ => {}
+        }
+        args.last_mut().unwrap().extend([token.clone()]);
+    }
+    Some(args)
+}
+
+/// Name of a type shown in the errors, like `Vec<u8>` instead of `Vec < u8 >`
+fn type_name(ty: &TokenStream) -> String {
+    ty.to_string()
+        .replace(" :: ", "::")
+        .replace(" < ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+}
+
+fn parse_param(function: &str, idx: usize, param: &Param) -> TokenStream {
+    let Param { name, ty, kind, .. } = param;
+    let param_name = name.to_string();
+    let parse = |value: TokenStream, ty: &TokenStream| {
+        let ty_name = type_name(ty);
+        quote! {
+            srtemplate::prelude::validations::parse_argument::<#ty>(
+                #value, #function, #param_name, #ty_name,
+            )
+        }
+    };
+
+    match kind {
+        ParamKind::Required => {
+            let value = parse(quote!(&args[#idx]), &quote!(#ty));
+            quote_spanned! {ty.span() =>
+                let #name = #value?;
+            }
+        }
+        ParamKind::Optional(inner) => {
+            let value = parse(quote!(arg), inner);
+            quote_spanned! {ty.span() =>
+                let #name = args.get(#idx).map(|arg| #value).transpose()?;
+            }
+        }
+        ParamKind::Default(default) => {
+            let ty_tokens = quote!(#ty);
+            let value = parse(quote!(arg), &ty_tokens);
+            // a string is parsed like the arguments, any other expression is the value itself
+            let default = match default.clone().into_iter().next() {
+                Some(TokenTree::Literal(lit)) if lit.to_string().starts_with('"') => {
+                    let default = parse(default.clone(), &ty_tokens);
+                    quote!(#default?)
+                }
+                _ => quote!(#default),
+            };
+            quote_spanned! {ty.span() =>
+                let #name: #ty = match args.get(#idx) {
+                    Some(arg) => #value?,
+                    None => #default,
+                };
+            }
+        }
+        ParamKind::Variadic(inner) => {
+            let value = parse(quote!(arg), inner);
+            quote_spanned! {ty.span() =>
+                let #name = args
+                    .get(#idx..)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|arg| #value)
+                    .collect::<Result<Vec<#inner>, _>>()?;
+            }
+        }
+        ParamKind::Rest => quote_spanned! {ty.span() =>
+            let #name: &[String] = args.get(#idx..).unwrap_or_default();
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use venial::{parse_item, Item};
+
+    use super::*;
+
+    fn expand(tokens: TokenStream) -> Result<String, Error> {
+        match parse_item(tokens)? {
+            Item::Function(func) => {
+                gen_function(TokenStream::new(), func).map(|tokens| tokens.to_string())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn arity_checks() {
+        let tokens = expand(quote! {
+            fn pad(text: String, width: Option<usize>, #[default = "-"] fill: char) {}
+        })
+        .unwrap();
+        assert!(tokens.contains(
+            &quote!(srtemplate::prelude::validations::args_min_len(args, 1usize)?;).to_string()
+        ));
+        assert!(tokens.contains(
+            &quote!(srtemplate::prelude::validations::args_max_len(args, 3usize)?;).to_string()
+        ));
+
+        let tokens = expand(quote!(
+            fn join(sep: String, parts: &[String]) {}
+        ))
+        .unwrap();
+        assert!(tokens.contains(
+            &quote!(srtemplate::prelude::validations::args_min_len(args, 1usize)?;).to_string()
+        ));
+        assert!(!tokens.contains("args_max_len"));
+    }
+
+    #[test]
+    fn optional_and_variadic_params() {
+        let tokens = expand(quote! {
+            fn sum(#[default = 1] scale: i32, mut values: Vec<i32>) {}
+        })
+        .unwrap();
+
+        let expected = [
+            quote! {
+                let scale: i32 = match args.get(0usize) {
+                    Some(arg) => srtemplate::prelude::validations::parse_argument::<i32>(
+                        arg, "sum", "scale", "i32",
+                    )?,
+                    None => 1,
+                };
+            },
+            quote! {
+                let values = args
+                    .get(1usize..)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|arg| srtemplate::prelude::validations::parse_argument::<i32>(
+                        arg, "sum", "values", "i32",
+                    ))
+                    .collect::<Result<Vec<i32>, _>>()?;
+            },
+            quote!(
+                fn sum(scale: i32, mut values: Vec<i32>) -> srtemplate::prelude::FuncResult {}
+            ),
+        ];
+        for tokens_part in expected {
+            assert!(
+                tokens.contains(&tokens_part.to_string()),
+                "{tokens_part} not in {tokens}"
+            );
+        }
+    }
+
+    #[test]
+    fn raw_args_option() {
+        let func = |tokens: TokenStream| match parse_item(tokens).unwrap() {
+            Item::Function(func) => func,
+            _ => unreachable!(),
+        };
+        let count = || {
+            func(quote! {
+                fn count(first: String) {
+                    let args = vec![first];
+                    Ok(args.len().to_string())
+                }
+            })
+        };
+
+        // the body is not inspected, so its own `args` is left alone
+        let tokens = gen_function(TokenStream::new(), count())
+            .unwrap()
+            .to_string();
+        assert!(tokens.contains(&quote!(fn count(first: String) -> ).to_string()));
+        assert!(tokens.contains(&quote!(let result = count(first);).to_string()));
+
+        let tokens = gen_function(quote!(args), count()).unwrap().to_string();
+        assert!(tokens.contains(&quote!(fn count(first: String, args: &[String])).to_string()));
+        assert!(tokens.contains(&quote!(let result = count(first, args);).to_string()));
+
+        assert!(gen_function(quote!(rest), count()).is_err());
+        assert!(gen_function(
+            quote!(args),
+            func(quote! {
+                fn count(args: Vec<String>) {}
+            })
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn signature() {
+        let tokens = expand(quote! {
+            /// Pads a text
+            ///
+            ///  up to `width`
+            fn pad(text: String, width: Option<usize>, #[default = '-'] fill: char, rest: &[String]) {}
+        })
+        .unwrap();
+
+        let expected = [
+            quote!(#[doc = r" Pads a text"] #[doc = r""] #[doc = r"  up to `width`"] fn pad(args: &[String])),
+            quote! {
+                fn pad_signature() -> srtemplate::prelude::FunctionSignature {
+                    srtemplate::prelude::FunctionSignature::new("pad")
+                        .param("text", "String")
+                        .optional_param("width", "usize")
+                        .optional_param("fill", "char")
+                        .variadic_param("rest", "String")
+                        .description(
+                            [r" Pads a text", r"", r"  up to `width`"]
+                                .iter()
+                                .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                                .trim(),
+                        )
+                }
+            },
+        ];
+        for tokens_part in expected {
+            assert!(
+                tokens.contains(&tokens_part.to_string()),
+                "{tokens_part} not in {tokens}"
+            );
+        }
+
+        assert!(tokens.contains(
+            &quote! {
+                fn register_pad(ctx: &srtemplate::SrTemplate<'_>) {
+                    ctx.add_function_with_signature("pad", pad, pad_signature());
+                }
+            }
+            .to_string()
+        ));
+
+        let tokens = expand(quote!(
+            fn noop() {}
+        ))
+        .unwrap();
+        assert!(tokens
+            .contains(&quote!(srtemplate::prelude::FunctionSignature::new("noop")).to_string()));
+        assert!(!tokens.contains("description"));
+    }
+
+    #[test]
+    fn return_types() {
+        let result =
+            |ty: TokenStream| convert_result(&ty.into_iter().collect::<Vec<_>>()).to_string();
+
+        assert_eq!(result(quote!(FuncResult)), "result");
+        assert_eq!(
+            result(quote!(Result<String, FunctionError>)),
+            quote!(result.map(|value| value.to_string()).map_err(|error| error)).to_string()
+        );
+        assert!(result(quote!(Result<u8, std::io::Error>)).contains("RuntimeError"));
+        assert!(result(quote!(Option<u8>)).contains("unwrap_or_default"));
+        assert_eq!(
+            result(quote!(impl std::fmt::Display)),
+            quote!(Ok(std::string::ToString::to_string(&result))).to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_params() {
+        assert!(expand(quote!(
+            fn f(a: Option<u8>, b: u8) {}
+        ))
+        .is_err());
+        assert!(expand(quote!(
+            fn f(a: Vec<u8>, b: u8) {}
+        ))
+        .is_err());
+        assert!(expand(quote!(
+            fn f(a: &[String], b: Option<u8>) {}
+        ))
+        .is_err());
+        assert!(expand(quote!(
+            fn f(#[default] a: u8) {}
+        ))
+        .is_err());
+        assert!(expand(quote!(
+            fn f(&self) {}
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn generic_args() {
+        let ty = |tokens: TokenStream| tokens.into_iter().collect::<Vec<_>>();
+        let args = |ty: &[TokenTree], name| {
+            super::generic_args(ty, name).map(|args| args.iter().map(type_name).collect::<Vec<_>>())
+        };
+
+        assert_eq!(
+            args(&ty(quote!(std::option::Option<u8>)), "Option"),
+            Some(vec!["u8".to_owned()])
+        );
+        assert_eq!(
+            args(&ty(quote!(Result<Vec<u8>, my::Error>)), "Result"),
+            Some(vec!["Vec<u8>".to_owned(), "my::Error".to_owned()])
+        );
+        assert_eq!(args(&ty(quote!(Vec<u8>)), "Option"), None);
+        assert_eq!(args(&ty(quote!(u8)), "Option"), None);
     }
 }
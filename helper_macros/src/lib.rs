@@ -6,6 +6,9 @@
 //!
 //! - **Function attribute macro**: Transforms regular functions into argument-parsing functions
 //! - **Variable derive macro**: Converts structs into collections of key-value variables
+//! - **Template macros**: Parse templates at compile time with `template!` and `include_template!`
+//! - **Compile template macro**: Compiles templates into Rust functions with `compile_template!`
+//! - **Template derive macro**: Binds a struct to a template checked at compile time
 //! - **Text case conversion**: Comprehensive text case handling utilities
 //!
 //! ## Macros
@@ -25,13 +28,18 @@
 //! **Generated Code**:
 //! ```rust
 //! fn greet(args: &[String]) -> srtemplate::prelude::FuncResult {
+//!     fn greet(name: String, age: i32) -> String {
+//!         format!("Hello {}, you're {} years old", name, age)
+//!     }
+//!
 //!     srtemplate::prelude::validations::args_min_len(args, 2)?;
 //!     srtemplate::prelude::validations::args_max_len(args, 2)?;
 //!
-//!     let name = args[0].parse::<String>().map_err(|_| srtemplate::prelude::FromArgsError::ParseFailed(0))?;
-//!     let age = args[1].parse::<i32>().map_err(|_| srtemplate::prelude::FromArgsError::ParseFailed(1))?;
+//!     let name = srtemplate::prelude::validations::parse_argument::<String>(&args[0], "greet", "name", "String")?;
+//!     let age = srtemplate::prelude::validations::parse_argument::<i32>(&args[1], "greet", "age", "i32")?;
 //!
-//!     format!("Hello {}, you're {} years old", name, age)
+//!     let result = greet(name, age);
+//!     Ok(std::string::ToString::to_string(&result))
 //! }
 //! ```
 //!
@@ -54,10 +62,15 @@
 //! ```rust
 //! impl<'variable> srtemplate::Variable<'variable> for User {
 //!     fn variables(&self) -> impl Iterator<Item = (std::borrow::Cow<'variable, str>, String)> {
-//!         [
-//!             ("user.first_name".into(), self.name.to_string()),
-//!             ("user.age".into(), self.age.to_string())
-//!         ].into_iter()
+//!         srtemplate::Variable::fields(self)
+//!             .map(|(name, value)| (srtemplate::join_name("user", name), value))
+//!     }
+//!
+//!     fn fields(&self) -> impl Iterator<Item = (std::borrow::Cow<'variable, str>, String)> {
+//!         let mut fields: Vec<(std::borrow::Cow<'variable, str>, String)> = Vec::new();
+//!         fields.push(("first_name".into(), self.name.to_string()));
+//!         fields.push(("age".into(), self.age.to_string()));
+//!         fields.into_iter()
 //!     }
 //! }
 //! ```
@@ -102,13 +115,17 @@
 //!    - Requires explicit type annotations
 //!
 //! 2. `#[derive(Variable)]` macro:
-//!    - Currently only supports structs (not enums)
+//!    - Supports structs and enums, not unions
 //!    - Field types must implement `ToString`
+use compile::gen_compiled;
 use function::gen_function;
 use proc_macro::TokenStream;
-use venial::{parse_item, Error, Item};
+use template::{gen_template, Source};
+use venial::{parse_item, Item};
 
+mod compile;
 mod function;
+mod template;
 mod variable;
 
 /// # Function Macro Documentation
@@ -119,9 +136,9 @@ mod variable;
 ///
 /// The macro transforms a Rust function into one that:
 /// - Takes `&[String]` as input
-/// - Validates argument count matches parameter count
+/// - Validates argument count against the required and optional parameters
 /// - Parses each argument to the expected type
-/// - Returns a `FuncResult` (`Result<String, FunctionError>`)
+/// - Converts the returned value into a `FuncResult` (`Result<String, FunctionError>`)
 ///
 /// ## Usage
 ///
@@ -138,16 +155,114 @@ mod variable;
 ///
 /// ```rust
 /// fn add(args: &[String]) -> srtemplate::prelude::FuncResult {
+///     fn add(a: i32, b: i32) -> srtemplate::prelude::FuncResult {
+///         Ok((a + b).to_string())
+///     }
+///
 ///     srtemplate::prelude::validations::args_min_len(args, 2)?;
 ///     srtemplate::prelude::validations::args_max_len(args, 2)?;
 ///
-///     let a = args[0].parse::<i32>().map_err(|_| srtemplate::prelude::FromArgsError::ParseFailed(0))?;
-///     let b = args[1].parse::<i32>().map_err(|_| srtemplate::prelude::FromArgsError::ParseFailed(1))?;
+///     let a = srtemplate::prelude::validations::parse_argument::<i32>(&args[0], "add", "a", "i32")?;
+///     let b = srtemplate::prelude::validations::parse_argument::<i32>(&args[1], "add", "b", "i32")?;
 ///
-///     Ok((a + b).to_string())
+///     let result = add(a, b);
+///     result
+/// }
+/// ```
+///
+/// ### Return Types
+///
+/// Without return type, the body must return a `FuncResult`. Otherwise the returned value is
+/// converted into one:
+///
+/// | Return type | Result |
+/// |-------------|--------|
+/// | `FuncResult` | Returned as it is |
+/// | `Result<T, FunctionError>` | `T` converted with `to_string()` |
+/// | `Result<T, E>` | `T` converted with `to_string()`, `E` into a `FunctionError::RuntimeError` |
+/// | `Option<T>` | `T` converted with `to_string()`, or an empty string for `None` |
+/// | `String`, `impl Display` or any other type | Converted with `to_string()` |
+///
+/// ```rust
+/// #[function]
+/// fn divide(a: f64, b: f64) -> Result<f64, String> {
+///     if b == 0.0 {
+///         return Err("division by zero".to_owned());
+///     }
+///     Ok(a / b)
+/// }
+/// ```
+///
+/// ### Optional Parameters
+///
+/// `Option<T>` parameters are `None` when the argument is not given, and parameters with a
+/// `#[default = ...]` attribute take that value. A string default is parsed like the
+/// arguments, any other expression is used as the value:
+///
+/// ```rust
+/// #[function]
+/// fn pad(text: String, width: Option<usize>, #[default = "-"] fill: char) {
+///     let padding = width.unwrap_or(0).saturating_sub(text.len());
+///     Ok(text + &fill.to_string().repeat(padding))
+/// }
+/// ```
+///
+/// Optional parameters must come after the required ones, so `pad` accepts from 1 to 3
+/// arguments.
+///
+/// ### Variadic Parameters
+///
+/// A trailing `Vec<T>` parameter collects the remaining arguments parsed into `T`, and a
+/// trailing `&[String]` collects them as they are. There is no maximum number of arguments:
+///
+/// ```rust
+/// #[function]
+/// fn join(separator: String, parts: &[String]) {
+///     Ok(parts.join(&separator))
 /// }
 /// ```
 ///
+/// ### Raw Arguments
+///
+/// With `#[function(args)]` the body can also read the arguments as they were given through
+/// `args`, like the bodies written before the parameters were parsed. Without it `args` is not
+/// in scope, so those bodies fail to compile until the option is added:
+///
+/// ```rust
+/// #[function(args)]
+/// fn shout(times: u8) {
+///     Ok(format!("{}{}", args[0], "!".repeat(times as usize)))
+/// }
+/// ```
+///
+/// ### Signature
+///
+/// The macro also generates a `<name>_signature` function returning the `FunctionSignature`
+/// of the function: its parameters with their types, whether they are optional or variadic,
+/// and its doc comments as description. Registering it lets `SrTemplate::validate` check the
+/// number of arguments and `SrTemplate::functions` list it.
+///
+/// A `register_<name>` function adds the function along with its signature, using the name of
+/// the Rust function. Use `SrTemplate::add_function_with_signature` to register it with
+/// another name:
+///
+/// ```rust
+/// /// Pads a text with `fill` up to `width`
+/// #[function]
+/// fn pad(text: String, width: Option<usize>, #[default = "-"] fill: char) {
+///     let padding = width.unwrap_or(0).saturating_sub(text.len());
+///     Ok(text + &fill.to_string().repeat(padding))
+/// }
+///
+/// let ctx = SrTemplate::default();
+/// register_pad(&ctx);
+/// ctx.add_function_with_signature("fill", pad, pad_signature());
+///
+/// let signature = ctx.signature("pad").unwrap();
+/// assert_eq!(signature.to_string(), "pad(text: String, width?: usize, fill?: char)");
+/// assert_eq!(signature.description.as_deref(), Some("Pads a text with `fill` up to `width`"));
+/// ```
+///
 /// ### Visibility
 ///
 /// The macro preserves the original function's visibility:
@@ -163,7 +278,9 @@ mod variable;
 ///
 /// The generated function automatically handles:
 /// - Argument count validation (min and max)
-/// - Type parsing errors
+/// - Type parsing errors, as a `FunctionError::ParseArgument` naming the function, the
+///   parameter, the argument and the expected type, like
+///   ``Argument `b` of `divide` must be f64, but found "zero"``
 ///
 /// ## Implementation Details
 ///
@@ -171,9 +288,11 @@ mod variable;
 ///
 /// The macro performs several transformations:
 ///
-/// 1. **Argument Validation**: Adds checks for exact argument count matching parameter count
+/// 1. **Argument Validation**: Adds checks for the minimum and maximum argument count
 /// 2. **Parameter Parsing**: Generates code to parse each string argument to the corresponding parameter type
-/// 3. **Body Preservation**: Keeps the original function body intact
+/// 3. **Body Preservation**: Keeps the original function, with its body intact, inside the generated one
+/// 4. **Result Conversion**: Converts the value returned by the original function into a `FuncResult`
+/// 5. **Signature**: Generates `<name>_signature`, and keeps the doc comments on the generated function
 ///
 /// ### Restrictions
 ///
@@ -187,6 +306,8 @@ mod variable;
 /// - The function has a `self` parameter
 /// - Any parameter lacks a type annotation
 /// - The function lacks a body
+/// - A required parameter comes after an optional one
+/// - A variadic parameter is not the last one
 ///
 /// ## Technical Notes
 ///
@@ -197,17 +318,18 @@ mod variable;
 ///
 /// The generated code relies on these types being in scope:
 /// - `srtemplate::prelude::FuncResult`
-/// - `srtemplate::prelude::FromArgsError`
+/// - `srtemplate::prelude::FunctionError`
+/// - `srtemplate::prelude::FunctionSignature`
 /// - `srtemplate::prelude::validations` functions
 #[proc_macro_attribute]
-pub fn function(_: TokenStream, body: TokenStream) -> TokenStream {
+pub fn function(options: TokenStream, body: TokenStream) -> TokenStream {
     let func = match parse_item(body.into()) {
         Ok(Item::Function(func)) => Ok(func),
         Err(e) => Err(e),
-        Ok(_) => Err(Error::new("Just support functions")),
+        Ok(_) => Err(venial::Error::new("Just support functions")),
     };
 
-    func.and_then(gen_function)
+    func.and_then(|func| gen_function(options.into(), func))
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
@@ -266,6 +388,18 @@ pub fn function(_: TokenStream, body: TokenStream) -> TokenStream {
 ///   ```
 ///   Generates variable name `Config.MAX_SIZE`
 ///
+/// ### `#[template(prefix = false)]`
+/// - **Purpose**: Adds the fields without the struct name as prefix
+/// - **Example**:
+///   ```rust
+///   #[template(prefix = false)]
+///   struct Settings {
+///       lang: String,
+///   }
+///   ```
+///   Generates variable name `lang` instead of `Settings.lang`. The prefix can also be chosen
+///   when adding a value, with `SrTemplate::add_as` and `SrTemplate::add_flat`
+///
 /// ## Field-Level Attributes
 ///
 /// These attributes are applied to individual struct fields:
@@ -305,13 +439,65 @@ pub fn function(_: TokenStream, body: TokenStream) -> TokenStream {
 ///   ```
 ///   Generates variable name `Settings.max-file-size`
 ///
+/// ### `#[template(nested)]`
+/// - **Purpose**: Adds the variables of a field that implements `Variable` under the name of the field,
+///   instead of converting it into a string
+/// - **Example**:
+///   ```rust
+///   struct User {
+///       #[template(nested)]
+///       address: Address,
+///   }
+///   ```
+///   Generates variable names like `User.address.city`, where `city` is named by the
+///   attributes of `Address`
+///
+/// ### `#[template(flatten)]`
+/// - **Purpose**: Adds the variables of a field that implements `Variable` under the prefix
+///   of the struct, as if they were its own fields
+/// - **Example**:
+///   ```rust
+///   struct User {
+///       #[template(flatten)]
+///       contact: Contact,
+///   }
+///   ```
+///   Generates variable names like `User.phone`. A value without fields, like a unit struct
+///   or the variant of an enum, keeps the name of the field: `User.contact`
+///
+/// ### `#[template(default = "value")]`
+/// - **Purpose**: Value of an `Option` field when it is `None`, otherwise the field is omitted
+/// - **Example**:
+///   ```rust
+///   struct Order {
+///       #[template(default = "standard")]
+///       shipping: Option<String>,
+///   }
+///   ```
+///
+/// ### `#[template(with = "path::to::function")]`
+/// - **Purpose**: Converts the field with a function taking a reference to it and returning
+///   any `ToString` value, instead of `to_string()`
+/// - **Example**:
+///   ```rust
+///   struct Order {
+///       #[template(with = "format_money")]
+///       total: f32,
+///   }
+///
+///   fn format_money(total: &f32) -> String {
+///       format!("${total:.2}")
+///   }
+///   ```
+///
 /// ## Generated Implementation
 ///
 /// The macro generates an implementation of the `Variable` trait that provides:
 ///
 /// 1. A `variables()` method returning an iterator of `(Cow<str>, String)` tuples
-/// 2. Proper case conversion according to the specified attributes
-/// 3. Field filtering for ignored fields
+/// 2. A `fields()` method returning the same variables without the prefix, used to nest values
+/// 3. Proper case conversion according to the specified attributes
+/// 4. Field filtering for ignored fields
 ///
 /// ### Example Output
 ///
@@ -333,15 +519,60 @@ pub fn function(_: TokenStream, body: TokenStream) -> TokenStream {
 ///
 /// ## Supported Field Types
 ///
+/// Fields are converted with `to_string()`, except:
+/// - `Option<T>`: the inner value is used, and the field is omitted when it is `None`
+/// - `Vec<T>`, `VecDeque<T>`, `LinkedList<T>`, `HashSet<T>` and `BTreeSet<T>`: one variable per
+///   item, named by index like `Order.items.0`
+/// - `HashMap<K, V>` and `BTreeMap<K, V>`: one variable per entry, named by key like `Order.prices.apple`
+///
+/// These types are recognized by name, so aliases of them are converted with `to_string()`.
+/// They can be combined, like `Option<Vec<String>>`, and the items can be `nested` or `flatten`.
+///
 /// The macro works with:
 /// - Named fields (standard structs)
 /// - Tuple structs (fields accessed by index)
 /// - Unit structs (treated as a single value)
 ///
+/// ## Enums
+///
+/// The enum name is the prefix, like the struct name, and its value is the name of the
+/// variant, converted with `case_fields`. The fields of the variant are added under the
+/// prefix. Variants accept the `rename`, `alias`, `case` and `ignore` attributes of fields.
+///
+/// ```rust
+/// #[derive(srtemplate::Variable)]
+/// enum Status {
+///     Online,
+///     #[template(rename = "away")]
+///     Idle { minutes: u32 },
+/// }
+/// ```
+///
+/// `Status::Online` produces `Status = "online"` and `Status::Idle { minutes: 5 }`
+/// produces `Status = "away"` and `Status.minutes = "5"`.
+///
+/// ## Generics
+///
+/// Generic structs and enums are supported, including lifetimes and const generics. The
+/// implementation requires `ToString` for the fields whose type uses a type parameter, or
+/// `Variable` if they are `nested` or `flatten`:
+///
+/// ```rust
+/// #[derive(srtemplate::Variable)]
+/// struct Pair<'a, T, const N: usize> {
+///     name: &'a str,
+///     values: Vec<T>, // requires `T: ToString`
+///     #[template(ignore)]
+///     cache: [u8; N],
+/// }
+/// ```
+///
+/// Fields converted `with` a function are not bounded, the function must declare them.
+///
 /// ## Error Handling
 ///
 /// The macro will error if:
-/// - Applied to non-struct types (enums, unions, etc.)
+/// - Applied to unions
 /// - Invalid case type is specified
 /// - Attribute syntax is malformed
 #[proc_macro_derive(Variable, attributes(template))]
@@ -351,7 +582,184 @@ pub fn derive_variable(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[derive(Clone, Copy, Debug)]
+/// # Template Macro Documentation
+///
+/// Parses a template at compile time and expands to a `srtemplate::ast::ParsedTemplate`,
+/// which is rendered with `SrTemplate::render_parsed` without parsing it again at runtime.
+///
+/// ## Usage
+///
+/// ```rust
+/// let template = srtemplate::template!("Hi {{ toLower(name) }}!");
+///
+/// let ctx = srtemplate::SrTemplate::default();
+/// ctx.add_variable("name", &"WORLD");
+/// assert_eq!(ctx.render_parsed(&template).unwrap(), "Hi world!");
+/// ```
+///
+/// Custom delimiters are passed after the template:
+///
+/// ```rust
+/// let template = srtemplate::template!("Hi <% name %>!", "<%", "%>");
+/// ```
+///
+/// ## Error Handling
+///
+/// Syntax errors are reported as compile errors on the template literal, with the
+/// same message and location that `SrTemplate::render` would return at runtime.
+#[proc_macro]
+pub fn template(input: TokenStream) -> TokenStream {
+    gen_template(input.into(), Source::Inline)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// # Include Template Macro Documentation
+///
+/// Same as `template!`, but reads the template from a file, relative to the root of
+/// the crate (`CARGO_MANIFEST_DIR`). The file is embedded with `include_str!`, so
+/// changes to it trigger a rebuild.
+///
+/// ## Usage
+///
+/// ```rust
+/// let template = srtemplate::include_template!("templates/greeting.txt");
+/// let custom = srtemplate::include_template!("templates/page.html", "<%", "%>");
+/// ```
+///
+/// ## Error Handling
+///
+/// The macro will error if the file cannot be read or its syntax is invalid.
+#[proc_macro]
+pub fn include_template(input: TokenStream) -> TokenStream {
+    gen_template(input.into(), Source::File)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// # Compile Template Macro Documentation
+///
+/// Compiles a template into a Rust function, with a `write_str` call for each node and
+/// direct calls to the functions, so no template is interpreted at runtime.
+///
+/// ## Usage
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// srtemplate::compile_template!(pub fn greeting, "Hi {{ toUpper(name) }}!");
+///
+/// let mut out = String::new();
+/// greeting(&HashMap::from([("name", "world")]), &mut out).unwrap();
+/// assert_eq!(out, "Hi WORLD!");
+/// ```
+///
+/// This generates:
+///
+/// ```rust
+/// pub fn greeting(
+///     vars: &impl srtemplate::Variables,
+///     out: &mut impl std::fmt::Write,
+/// ) -> Result<(), srtemplate::Error> {
+///     const SOURCE: &str = "Hi {{ toUpper(name) }}!";
+///     out.write_str("Hi ")?;
+///     out.write_str(&srtemplate::compiled::function(
+///         srtemplate::builtin::text::to_upper(&[String::from(
+///             srtemplate::compiled::variable(vars, "name", SOURCE, 15..19)?,
+///         )]),
+///         SOURCE,
+///         6..20,
+///     )?)?;
+///     out.write_str("!")?;
+///     Ok(())
+/// }
+/// ```
+///
+/// Attributes and doc comments before `fn` are kept. The template can be read from a
+/// file, relative to the root of the crate, and use custom delimiters:
+///
+/// ```rust
+/// srtemplate::compile_template!(fn page, path = "templates/page.html", "<%", "%>");
+/// ```
+///
+/// ## Functions
+///
+/// The builtin functions are called from `srtemplate::builtin`, so their features must be
+/// enabled. Any other function is called by name, so a function with the same name and the
+/// signature of `#[function]` functions must be in scope.
+///
+/// ## Error Handling
+///
+/// The macro will error if the file cannot be read or its syntax is invalid. The generated
+/// function returns the same errors as `SrTemplate::render` for missing variables and
+/// failed functions, and `Error::Write` if the output cannot be written.
+#[proc_macro]
+pub fn compile_template(input: TokenStream) -> TokenStream {
+    gen_compiled(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// # Template Derive Macro Documentation
+///
+/// This procedural macro binds a struct to a template, checked at compile time, and
+/// generates a `render(&self, &SrTemplate)` method and a `Display` implementation.
+///
+/// ## Struct-Level Attributes
+///
+/// ### `#[template(source = "...")]` or `#[template(path = "...")]`
+/// - **Purpose**: The template, inline or in a file relative to the root of the crate
+/// - **Example**:
+///   ```rust
+///   #[derive(srtemplate::Template)]
+///   #[template(source = "Hi {{ toUpper(name) }}, you have {{ unread }} messages")]
+///   struct Inbox {
+///       name: String,
+///       unread: u32,
+///   }
+///   ```
+///
+/// ### `#[template(start = "...", close = "...")]`
+/// - **Purpose**: Delimiters of the template, `{{` and `}}` by default
+///
+/// ## Field-Level Attributes
+///
+/// The `ignore`, `rename` and `alias` attributes of `#[derive(Variable)]` are also used
+/// here, so both derives can be used on the same struct.
+///
+/// ## Generated Implementation
+///
+/// The fields are the variables of the template, referenced by name (`{{ name }}`), and
+/// only the referenced fields need to implement `ToString`. The variables of the context
+/// are also available, but fields take precedence:
+///
+/// ```rust
+/// let ctx = srtemplate::SrTemplate::default();
+/// let inbox = Inbox { name: "Sergio".into(), unread: 3 };
+///
+/// assert_eq!(inbox.render(&ctx).unwrap(), "Hi SERGIO, you have 3 messages");
+/// assert_eq!(inbox.to_string(), "Hi SERGIO, you have 3 messages");
+/// ```
+///
+/// `Display` renders with a context built once with `SrTemplate::default()`, so only the
+/// builtin functions are available. If the template calls any other function or a function
+/// fails, formatting returns `fmt::Error` and `to_string()` panics, use `render` to get the
+/// error instead.
+///
+/// ## Error Handling
+///
+/// The macro will error if:
+/// - Applied to non-struct types or tuple structs
+/// - The syntax of the template is invalid
+/// - The template references a variable that is not a field of the struct
+#[proc_macro_derive(Template, attributes(template))]
+pub fn derive_template(input: TokenStream) -> TokenStream {
+    template::derive(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub(crate) enum TextCase {
     Lower,
     Upper,
@@ -388,11 +796,9 @@ impl TextCase {
                 }
             } else if c.is_uppercase() {
                 if let Some(prev) = prev_char {
-                    if prev.is_lowercase() || prev.is_ascii_digit() {
-                        if !current_word.is_empty() {
-                            words.push(current_word);
-                            current_word = String::new();
-                        }
+                    if (prev.is_lowercase() || prev.is_ascii_digit()) && !current_word.is_empty() {
+                        words.push(current_word);
+                        current_word = String::new();
                     }
                 }
                 current_word.push(c);
@@ -476,7 +882,13 @@ impl std::str::FromStr for TextCase {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        let normalized = s
+            .chars()
+            .filter(|c| !matches!(c, '_' | '-' | ' '))
+            .collect::<String>()
+            .to_lowercase();
+
+        match normalized.as_str() {
             "lower" | "lowercase" => Ok(TextCase::Lower),
             "upper" | "uppercase" => Ok(TextCase::Upper),
             "sentence" | "sentencecase" => Ok(TextCase::Sentence),
@@ -484,9 +896,7 @@ impl std::str::FromStr for TextCase {
             "camel" | "camelcase" => Ok(TextCase::Camel),
             "pascal" | "pascalcase" => Ok(TextCase::Pascal),
             "kebab" | "kebabcase" => Ok(TextCase::Kebab),
-            "screamingsnake" | "screaming_snake" | "screaming-snake" | "screaming snake" => {
-                Ok(TextCase::ScreamingSnake)
-            }
+            "screamingsnake" | "screamingsnakecase" => Ok(TextCase::ScreamingSnake),
             _ => Err(format!("Invalid text case: {}", s)),
         }
     }
@@ -536,7 +946,7 @@ mod tests {
 
         assert_eq!(
             TextCase::Lower.convert(input),
-            "hello_world-example 123httpprequest"
+            "hello_world-example 123httprequest"
         );
         assert_eq!(
             TextCase::Upper.convert(input),
@@ -0,0 +1,519 @@
+use std::ops::Range;
+
+use proc_macro2::{Literal, Span, TokenStream, TokenTree};
+use quote::quote;
+use venial::{
+    parse_item, Attribute, Error, Fields, Item, NamedField, Struct,
+    WhereClausePredicate as WherePredicate,
+};
+
+use srtemplate_parser::{parse, Snippet, TemplateNode, Visitor};
+
+use crate::variable::parse_template_attribute;
+
+/// Where the text of the template comes from
+pub enum Source {
+    /// The template is the string literal
+    Inline,
+    /// The string literal is the path of the template, relative to the crate root
+    File,
+}
+
+pub fn gen_template(input: TokenStream, source: Source) -> Result<TokenStream, Error> {
+    let (args, spans) = parse_arguments(input)?;
+    let (value, start, close) = match args.as_slice() {
+        [value] => (value.as_str(), "{{", "}}"),
+        [value, start, close] => (value.as_str(), start.as_str(), close.as_str()),
+        _ => {
+            return Err(Error::new(
+                "expected a template and, optionally, its start and close delimiters",
+            ))
+        }
+    };
+
+    let template = Template::load(value, spans[0], source)?;
+    let nodes = template.parse(start, close)?;
+
+    Ok(template.expand(&nodes))
+}
+
+pub fn derive(input: TokenStream) -> Result<TokenStream, Error> {
+    let struct_decl = match parse_item(input) {
+        Ok(Item::Struct(struct_decl)) => struct_decl,
+        Err(e) => return Err(e),
+        Ok(_) => {
+            return Err(Error::new(
+                "currently only structs are supported by this derive",
+            ))
+        }
+    };
+
+    let mut source = None;
+    let mut delimiters = ("{{".to_owned(), "}}".to_owned());
+    for attr in &struct_decl.attributes {
+        for (key, value, span) in parse_string_attribute(attr)? {
+            match key.as_str() {
+                "source" if source.is_none() => source = Some((value, span, Source::Inline)),
+                "path" if source.is_none() => source = Some((value, span, Source::File)),
+                "source" | "path" => {
+                    return Err(Error::new_at_span(
+                        span,
+                        "only one of `source` and `path` can be used",
+                    ))
+                }
+                "start" => delimiters.0 = value,
+                "close" => delimiters.1 = value,
+                _ => {}
+            }
+        }
+    }
+    let Some((value, span, source)) = source else {
+        return Err(Error::new(
+            "expected `#[template(source = \"...\")]` or `#[template(path = \"...\")]`",
+        ));
+    };
+
+    let fields = named_fields(&struct_decl)?;
+    let template = Template::load(&value, span, source)?;
+    let nodes = template.parse(&delimiters.0, &delimiters.1)?;
+
+    let mut references = References::default();
+    references.visit_nodes(&nodes);
+
+    let mut variables = Vec::new();
+    for (name, span) in references.0 {
+        let Some((_, field)) = fields.iter().find(|(field, _)| field == name) else {
+            let message = format!("Variable not found: {name}");
+            let help = format!("help: `{}` has no field named \"{name}\"", struct_decl.name);
+            let mut snippet = Snippet::at(&template.text, span, &message).colored(false);
+            snippet.help = &help;
+            return Err(Error::new_at_span(template.span, snippet.to_string()));
+        };
+        if !variables.iter().any(|(variable, _)| *variable == name) {
+            variables.push((name, field));
+        }
+    }
+
+    let name = &struct_decl.name;
+    let generic_params = &struct_decl.generic_params;
+    let generic_args = struct_decl.get_inline_generic_args();
+    // only the referenced fields need to be converted into strings
+    let where_clause = variables.iter().fold(
+        struct_decl.where_clause.clone().unwrap_or_default(),
+        |clause, (_, field)| {
+            let ty = &field.ty;
+            clause.with_predicate(WherePredicate::parse(quote!(#ty: std::string::ToString)))
+        },
+    );
+    let expr = template.expand(&nodes);
+    let len = variables.len();
+    let variables = variables.iter().map(|(name, field)| {
+        let field = &field.name;
+        quote!((#name, self.#field.to_string()))
+    });
+
+    Ok(quote! {
+        impl #generic_params #name #generic_args #where_clause {
+            /// Renders the template of this struct, using its fields as variables
+            pub fn render(&self, ctx: &srtemplate::SrTemplate) -> Result<String, srtemplate::Error> {
+                static TEMPLATE: std::sync::OnceLock<srtemplate::ast::ParsedTemplate<'static>> =
+                    std::sync::OnceLock::new();
+                let template = TEMPLATE.get_or_init(|| #expr);
+                let variables: [(&str, String); #len] = [#(#variables),*];
+
+                ctx.render_parsed_with(template, variables)
+            }
+        }
+
+        /// Renders the template with the builtin functions only
+        ///
+        /// Formatting fails if the template calls any other function or a function fails,
+        /// so `to_string()` panics; use `render` to get the error instead.
+        impl #generic_params std::fmt::Display for #name #generic_args #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let rendered = self
+                    .render(srtemplate::compiled::default_context())
+                    .map_err(|_| std::fmt::Error)?;
+                f.write_str(&rendered)
+            }
+        }
+    })
+}
+
+/// Names of the fields usable in the template and their identifiers
+fn named_fields(struct_decl: &Struct) -> Result<Vec<(String, &NamedField)>, Error> {
+    let fields = match &struct_decl.fields {
+        Fields::Named(fields) => fields,
+        Fields::Unit => return Ok(Vec::new()),
+        Fields::Tuple(_) => {
+            return Err(Error::new(
+                "tuple structs are not supported by this derive, their fields cannot be referenced in a template",
+            ))
+        }
+    };
+
+    let mut named = Vec::new();
+    for (field, _) in fields.fields.iter() {
+        let mut name = Some(field.name.to_string());
+        for attr in &field.attributes {
+            for (key, value) in parse_template_attribute(attr)? {
+                match key.as_str() {
+                    "ignore" => name = None,
+                    "rename" | "alias" if name.is_some() => name = Some(value),
+                    _ => {}
+                }
+            }
+        }
+        if let Some(name) = name {
+            named.push((name, field));
+        }
+    }
+
+    Ok(named)
+}
+
+/// The `key = "value"` pairs of a `#[template(...)]` attribute
+fn parse_string_attribute(attr: &Attribute) -> Result<Vec<(String, String, Span)>, Error> {
+    if !attr
+        .get_single_path_segment()
+        .is_some_and(|n| n == "template")
+    {
+        return Ok(Vec::new());
+    }
+
+    let mut attributes = Vec::new();
+    let mut key = None;
+    for token in attr.get_value_tokens() {
+        match token {
+            TokenTree::Ident(ident) => key = Some(ident.to_string()),
+            TokenTree::Literal(lit) => {
+                let Some(key) = key.take() else { continue };
+                let value = string_value(&lit.to_string())
+                    .ok_or_else(|| Error::new_at_span(lit.span(), "expected a string literal"))?;
+                attributes.push((key, value, lit.span()));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// Variables referenced by a template
+#[derive(Default)]
+struct References<'a>(Vec<(&'a str, Range<usize>)>);
+
+impl<'a> Visitor<'a> for References<'a> {
+    fn visit_variable(&mut self, name: &'a str, span: &Range<usize>) {
+        self.0.push((name, span.clone()));
+    }
+}
+
+/// Text of a template and the expression of its source in the generated code
+pub(crate) struct Template {
+    pub text: String,
+    pub source: TokenStream,
+    pub span: Span,
+}
+
+impl Template {
+    pub fn load(value: &str, span: Span, source: Source) -> Result<Self, Error> {
+        let (text, source) = match source {
+            Source::Inline => (value.to_owned(), quote!(#value)),
+            Source::File => {
+                let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+                let path = format!("{root}/{value}");
+                let text = std::fs::read_to_string(&path)
+                    .map_err(|e| Error::new_at_span(span, format!("Failed to read {path}: {e}")))?;
+                (text, quote!(include_str!(#path)))
+            }
+        };
+
+        Ok(Self { text, source, span })
+    }
+
+    pub fn parse(&self, start: &str, close: &str) -> Result<Vec<TemplateNode<'_>>, Error> {
+        // compiler messages do not support colors
+        parse(&self.text, start, close)
+            .map_err(|e| Error::new_at_span(self.span, e.snippet().colored(false).to_string()))
+    }
+
+    /// Expression of the parsed template, a `srtemplate::ast::ParsedTemplate`
+    fn expand(&self, nodes: &[TemplateNode]) -> TokenStream {
+        let source = &self.source;
+        let nodes = nodes.iter().map(node_tokens);
+
+        quote! {
+            {
+                static SOURCE: &str = #source;
+                srtemplate::ast::ParsedTemplate::from_parts(SOURCE, vec![#(#nodes),*])
+            }
+        }
+    }
+}
+
+/// Builds the expression of a node, taking its text from the `SOURCE` static
+fn node_tokens(node: &TemplateNode) -> TokenStream {
+    let span = node.span();
+    let (span_start, span_end) = (usize_literal(span.start), usize_literal(span.end));
+    let slice = {
+        // the text starts the span, except for strings whose span includes the quote
+        let offset = match node {
+            TemplateNode::String(..) => span.start + 1,
+            _ => span.start,
+        };
+        let (start, end) = (
+            usize_literal(offset),
+            usize_literal(offset + node.text().len()),
+        );
+        quote!(&SOURCE[#start..#end])
+    };
+
+    let variant = match node {
+        TemplateNode::Variable(..) => quote!(Variable(#slice)),
+        TemplateNode::Function(_, args, _) => {
+            let args = args.iter().map(node_tokens);
+            quote!(Function(#slice, vec![#(#args),*]))
+        }
+        TemplateNode::String(..) => quote!(String(#slice)),
+        TemplateNode::Number(..) => quote!(Number(#slice)),
+        TemplateNode::Float(..) => quote!(Float(#slice)),
+        TemplateNode::RawText(..) => quote!(RawText(#slice)),
+    };
+
+    // the span is the last field of every variant
+    let mut tokens = quote!(srtemplate::ast::TemplateNode::);
+    let mut variant = variant.into_iter().collect::<Vec<_>>();
+    if let Some(TokenTree::Group(group)) = variant.pop() {
+        let inner = group.stream();
+        tokens.extend(variant);
+        tokens.extend(quote!((#inner, #span_start..#span_end)));
+    }
+    tokens
+}
+
+pub(crate) fn usize_literal(n: usize) -> Literal {
+    Literal::usize_unsuffixed(n)
+}
+
+/// Parses the comma separated string literals of the macro
+pub(crate) fn parse_arguments(input: TokenStream) -> Result<(Vec<String>, Vec<Span>), Error> {
+    let mut values = Vec::new();
+    let mut spans = Vec::new();
+    let mut expect_literal = true;
+
+    for token in input {
+        match token {
+            TokenTree::Literal(lit) if expect_literal => {
+                let value = string_value(&lit.to_string())
+                    .ok_or_else(|| Error::new_at_span(lit.span(), "expected a string literal"))?;
+                values.push(value);
+                spans.push(lit.span());
+                expect_literal = false;
+            }
+            TokenTree::Punct(p) if !expect_literal && p.as_char() == ',' => {
+                expect_literal = true;
+            }
+            // literals can be wrapped in invisible groups when passed through `macro_rules!`
+            TokenTree::Group(group)
+                if expect_literal && group.delimiter() == proc_macro2::Delimiter::None =>
+            {
+                let (inner, inner_spans) = parse_arguments(group.stream())?;
+                values.extend(inner);
+                spans.extend(inner_spans);
+                expect_literal = false;
+            }
+            token => {
+                return Err(Error::new_at_span(
+                    token.span(),
+                    "expected a string literal",
+                ))
+            }
+        }
+    }
+
+    if values.is_empty() {
+        return Err(Error::new("expected a template"));
+    }
+
+    Ok((values, spans))
+}
+
+/// Value of a string literal token, like `"a\n"` or `r#"a"#`
+fn string_value(lit: &str) -> Option<String> {
+    if let Some(raw) = lit.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = &raw[hashes..raw.len() - hashes];
+        return raw.strip_prefix('"')?.strip_suffix('"').map(str::to_owned);
+    }
+
+    let lit = lit.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(lit.len());
+    let mut chars = lit.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            '\\' => value.push('\\'),
+            '\'' => value.push('\''),
+            '"' => value.push('"'),
+            'x' => {
+                let code = chars.next()?.to_digit(16)? * 16 + chars.next()?.to_digit(16)?;
+                value.push(char::from_u32(code)?);
+            }
+            'u' => {
+                chars.next().filter(|c| *c == '{')?;
+                let code = chars
+                    .by_ref()
+                    .take_while(|c| *c != '}')
+                    .filter(|c| *c != '_')
+                    .collect::<String>();
+                value.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            }
+            // line continuation, skips the new line and the leading whitespace
+            '\n' => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            _ => return None,
+        }
+    }
+
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_values() {
+        assert_eq!(string_value(r#""a {{ b }}""#).as_deref(), Some("a {{ b }}"));
+        assert_eq!(
+            string_value(r#""\"q\"\n\t\\ \x41\u{1F600}""#).as_deref(),
+            Some("\"q\"\n\t\\ A\u{1F600}")
+        );
+        assert_eq!(string_value("\"a \\\n    b\"").as_deref(), Some("a b"));
+        assert_eq!(
+            string_value(r###"r#"{{ f("x") }}"#"###).as_deref(),
+            Some(r#"{{ f("x") }}"#)
+        );
+        assert_eq!(string_value("b\"bytes\""), None);
+        assert_eq!(string_value("10"), None);
+    }
+
+    #[test]
+    fn expand_template() {
+        let tokens = gen_template(quote!("Hi {{ trim(name, \"x\") }}"), Source::Inline).unwrap();
+        let expected = quote! {
+            {
+                static SOURCE: &str = "Hi {{ trim(name, \"x\") }}";
+                srtemplate::ast::ParsedTemplate::from_parts(SOURCE, vec![
+                    srtemplate::ast::TemplateNode::RawText(&SOURCE[0..3], 0..3),
+                    srtemplate::ast::TemplateNode::Function(
+                        &SOURCE[6..10],
+                        vec![
+                            srtemplate::ast::TemplateNode::Variable(&SOURCE[11..15], 11..15),
+                            srtemplate::ast::TemplateNode::String(&SOURCE[18..19], 17..20)
+                        ],
+                        6..21
+                    )
+                ])
+            }
+        };
+
+        assert_eq!(tokens.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn custom_delimiters() {
+        assert!(gen_template(quote!("<% x %>", "<%", "%>"), Source::Inline).is_ok());
+        assert!(gen_template(quote!("<% x %>", "<%"), Source::Inline).is_err());
+    }
+
+    #[test]
+    fn syntax_error() {
+        let error = gen_template(quote!("Hi {{ trim(\"x }}"), Source::Inline).unwrap_err();
+
+        assert!(error
+            .to_compile_error()
+            .to_string()
+            .contains("SyntaxError: Unterminated string literal"));
+    }
+
+    #[test]
+    fn missing_file() {
+        let error = gen_template(quote!("missing.html"), Source::File).unwrap_err();
+
+        assert!(error
+            .to_compile_error()
+            .to_string()
+            .contains("Failed to read"));
+    }
+
+    #[test]
+    fn derive_referenced_fields() {
+        let tokens = derive(quote! {
+            #[template(source = "{{ a }} {{ trim(b) }} {{ a }}")]
+            struct Fields {
+                a: u8,
+                #[template(rename = "b")]
+                c: String,
+                unused: Vec<u8>,
+            }
+        })
+        .unwrap()
+        .to_string();
+
+        assert!(tokens
+            .contains(&quote!([("a", self.a.to_string()), ("b", self.c.to_string())]).to_string()));
+        assert!(tokens.contains(&quote!(String: std::string::ToString).to_string()));
+        assert!(!tokens.contains("unused"));
+    }
+
+    #[test]
+    fn derive_unknown_field() {
+        let error = derive(quote! {
+            #[template(source = "Hi\n{{ trim(nme) }}")]
+            struct User {
+                name: String,
+            }
+        })
+        .unwrap_err()
+        .to_compile_error()
+        .to_string();
+
+        assert!(error.contains("Error: Variable not found: nme"));
+        assert!(error.contains(" --> 2:9"));
+        assert!(error.contains("help: `User` has no field named"));
+    }
+
+    #[test]
+    fn derive_invalid_input() {
+        let tuple = derive(quote! {
+            #[template(source = "{{ 0 }}")]
+            struct Tuple(u8);
+        });
+        let missing = derive(quote! {
+            struct Missing;
+        });
+        let both = derive(quote! {
+            #[template(source = "", path = "a.txt")]
+            struct Both;
+        });
+
+        assert!(tuple.is_err());
+        assert!(missing.is_err());
+        assert!(both.is_err());
+        assert!(derive(quote! {
+            #[template(source = "<% x %>", start = "<%", close = "%>")]
+            struct Delimiters { x: u8 }
+        })
+        .is_ok());
+    }
+}
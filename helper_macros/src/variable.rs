@@ -1,9 +1,12 @@
 use std::str::FromStr;
 
-use proc_macro2::{TokenStream, TokenTree};
-use quote::quote;
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{format_ident, quote};
 
-use venial::{parse_item, Attribute, AttributeValue, Error, Fields, Item, Struct};
+use venial::{
+    parse_item, Attribute, AttributeValue, Enum, Error, Fields, GenericParam, GenericParamList,
+    InlineGenericArgs, Item, Struct, WhereClause, WhereClausePredicate,
+};
 
 use crate::TextCase;
 
@@ -12,10 +15,10 @@ pub fn derive(input: TokenStream) -> Result<TokenStream, Error> {
 
     let res = match &type_decl {
         Ok(Item::Struct(struct_decl)) => derive_struct(struct_decl)?,
-        // Ok(Item::Enum(enum_decl)) => derive_enum(enum_decl)?,
+        Ok(Item::Enum(enum_decl)) => derive_enum(enum_decl)?,
         _ => {
             return Err(Error::new(
-                "currently only structs are supported by this derive",
+                "currently only structs and enums are supported by this derive",
             ))
         }
     };
@@ -23,7 +26,7 @@ pub fn derive(input: TokenStream) -> Result<TokenStream, Error> {
     Ok(res)
 }
 
-fn parse_template_attribute(attr: &Attribute) -> Result<Vec<(String, String)>, Error> {
+pub(crate) fn parse_template_attribute(attr: &Attribute) -> Result<Vec<(String, String)>, Error> {
     let mut attributes = Vec::new();
 
     if !attr
@@ -41,19 +44,24 @@ fn parse_template_attribute(attr: &Attribute) -> Result<Vec<(String, String)>, E
                 match token {
                     TokenTree::Ident(ident) => {
                         let ident_str = ident.to_string();
-                        if ident_str == "ignore" {
-                            attributes.push(("ignore".to_string(), "true".to_string()));
+                        // boolean values, like `prefix = false`
+                        if !current_key.is_empty() && matches!(ident_str.as_str(), "true" | "false")
+                        {
+                            attributes.push((current_key.clone(), ident_str));
+                            current_key.clear();
+                            continue;
+                        }
+                        if matches!(ident_str.as_str(), "ignore" | "nested" | "flatten") {
+                            attributes.push((ident_str, "true".to_string()));
                             continue;
                         }
                         current_key = ident_str;
                     }
                     TokenTree::Punct(p) if p.as_char() == '=' => {}
-                    TokenTree::Literal(lit) => {
-                        if !current_key.is_empty() {
-                            let current_value = lit.to_string().trim_matches('"').to_string();
-                            attributes.push((current_key.clone(), current_value));
-                            current_key.clear();
-                        }
+                    TokenTree::Literal(lit) if !current_key.is_empty() => {
+                        let current_value = lit.to_string().trim_matches('"').to_string();
+                        attributes.push((current_key.clone(), current_value));
+                        current_key.clear();
                     }
                     _ => {}
                 }
@@ -67,11 +75,414 @@ fn parse_template_attribute(attr: &Attribute) -> Result<Vec<(String, String)>, E
 
 fn derive_struct(struct_decl: &Struct) -> Result<TokenStream, Error> {
     let name_ident = struct_decl.name.clone();
+    let (prefix, field_case) = container_names(&name_ident, &struct_decl.attributes)?;
+
+    let mut predicates = Vec::new();
+    let fields = match &struct_decl.fields {
+        // the value itself, without a field name
+        Fields::Unit => vec![quote!(fields.push(("".into(), self.to_string()));)],
+        fields => named_fields(fields, field_case)?
+            .iter()
+            .map(|field| {
+                let member = &field.member;
+                field.predicates(&mut predicates);
+                field.push(quote!(&self.#member))
+            })
+            .collect(),
+    };
+
+    Ok(implementation(
+        &name_ident,
+        &prefix,
+        Generics {
+            params: struct_decl.generic_params.as_ref(),
+            args: struct_decl.get_inline_generic_args(),
+            where_clause: struct_decl.where_clause.as_ref(),
+            predicates,
+        },
+        quote!(#(#fields)*),
+    ))
+}
+
+fn derive_enum(enum_decl: &Enum) -> Result<TokenStream, Error> {
+    let name_ident = enum_decl.name.clone();
+    let (prefix, field_case) = container_names(&name_ident, &enum_decl.attributes)?;
+
+    let mut predicates = Vec::new();
+    let mut arms = Vec::new();
+    for (variant, _) in enum_decl.variants.iter() {
+        let variant_ident = &variant.name;
+        let Some(tag) = field_name(&variant.attributes, variant_ident.to_string(), field_case)?
+        else {
+            arms.push(quote!(Self::#variant_ident { .. } => {}));
+            continue;
+        };
+
+        let fields = named_fields(&variant.fields, field_case)?;
+        fields
+            .iter()
+            .for_each(|field| field.predicates(&mut predicates));
+        let bindings = fields
+            .iter()
+            .map(|field| format_ident!("field_{}", field.member.to_string()))
+            .collect::<Vec<_>>();
+        let members = fields.iter().map(|field| &field.member);
+        let pushes = fields
+            .iter()
+            .zip(&bindings)
+            .map(|(field, binding)| field.push(quote!(#binding)));
+
+        arms.push(quote! {
+            Self::#variant_ident { #(#members: #bindings,)* .. } => {
+                fields.push(("".into(), #tag.to_string()));
+                #(#pushes)*
+            }
+        });
+    }
+
+    Ok(implementation(
+        &name_ident,
+        &prefix,
+        Generics {
+            params: enum_decl.generic_params.as_ref(),
+            args: enum_decl.get_inline_generic_args(),
+            where_clause: enum_decl.where_clause.as_ref(),
+            predicates,
+        },
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        },
+    ))
+}
+
+/// Generics of the type deriving `Variable`
+struct Generics<'a> {
+    params: Option<&'a GenericParamList>,
+    args: Option<InlineGenericArgs<'a>>,
+    where_clause: Option<&'a WhereClause>,
+    /// Bounds of the types of the fields, added to the where clause of the type
+    predicates: Vec<TokenStream>,
+}
+
+/// Implementation of `Variable`, where `body` pushes the fields into the `fields` vector
+fn implementation(
+    name_ident: &Ident,
+    prefix: &str,
+    generics: Generics,
+    body: TokenStream,
+) -> TokenStream {
+    let mut params = generics.params.cloned().unwrap_or_default();
+    let type_params = params
+        .params
+        .iter()
+        .filter(|(param, _)| param.is_ty())
+        .map(|(param, _)| param.name.to_string())
+        .collect::<Vec<_>>();
+    // defaults are only allowed in the declaration of the type
+    for (param, _) in params.params.iter_mut() {
+        if let Some(bound) = &mut param.bound {
+            bound.tokens.truncate(default_position(&bound.tokens));
+        }
+    }
+    // the lifetime of the names is added before the parameters of the type
+    let params = params.with_param(GenericParam::lifetime("variable"));
+    let args = generics.args;
+    // only the types using type parameters are bounded, bounds of concrete types are
+    // checked by the compiler and with lifetimes they make `to_string` ambiguous
+    let where_clause = generics
+        .predicates
+        .into_iter()
+        .filter(|predicate| mentions(predicate.clone(), &type_params))
+        .fold(
+            generics.where_clause.cloned().unwrap_or_default(),
+            |clause, predicate| clause.with_predicate(WhereClausePredicate::parse(predicate)),
+        );
+
+    // without prefix, the value itself has no name
+    let variables = if prefix.is_empty() {
+        quote! {
+            srtemplate::Variable::fields(self).filter(|(name, _)| !name.is_empty())
+        }
+    } else {
+        quote! {
+            srtemplate::Variable::fields(self)
+                .map(|(name, value)| (srtemplate::join_name(#prefix, name), value))
+        }
+    };
+
+    quote! {
+        impl #params srtemplate::Variable<'variable> for #name_ident #args
+        #where_clause {
+            fn variables(&self) -> impl Iterator<Item = (std::borrow::Cow<'variable, str>, String)> {
+                #variables
+            }
+
+            fn fields(&self) -> impl Iterator<Item = (std::borrow::Cow<'variable, str>, String)> {
+                let mut fields: Vec<(std::borrow::Cow<'variable, str>, String)> = Vec::new();
+                #body
+                fields.into_iter()
+            }
+        }
+    }
+}
+
+/// Position of the default value in the bound of a generic parameter, like `= u8` in
+/// `T: Copy = u8`, or the length of the bound if there is no default
+fn default_position(bound: &[TokenTree]) -> usize {
+    let mut depth = 0;
+    for (i, token) in bound.iter().enumerate() {
+        match token {
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' && depth > 0 => depth -= 1,
+            TokenTree::Punct(p) if p.as_char() == '=' && depth == 0 => return i,
+            _ => {}
+        }
+    }
+    bound.len()
+}
+
+/// Whether `tokens` contain any of the identifiers in `names`
+fn mentions(tokens: TokenStream, names: &[String]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => names.iter().any(|name| ident == name),
+        TokenTree::Group(group) => mentions(group.stream(), names),
+        _ => false,
+    })
+}
+
+/// How the value of a field is turned into variables
+#[derive(Clone, Copy, PartialEq)]
+enum FieldKind {
+    /// A single variable with the value converted into a string
+    Value,
+    /// The variables of the field, which implements `Variable`, under the name of the field
+    Nested,
+    /// The variables of the field, which implements `Variable`, under the parent prefix
+    Flatten,
+}
+
+/// Shape of the type of a field, collections are exported as one variable per item
+#[derive(Clone, Debug)]
+enum ValueType {
+    /// Any other type, converted according to the [`FieldKind`]
+    Plain(TokenStream),
+    /// `Option<T>`, omitted when `None`
+    Option(Box<ValueType>),
+    /// `Vec<T>` and other sequences, exported by index like `items.0`
+    List(Box<ValueType>),
+    /// `HashMap<K, V>` and `BTreeMap<K, V>`, exported by key like `prices.apple`
+    Map(TokenStream, Box<ValueType>),
+}
+
+impl PartialEq for ValueType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Plain(a), Self::Plain(b)) => a.to_string() == b.to_string(),
+            (Self::Option(a), Self::Option(b)) | (Self::List(a), Self::List(b)) => a == b,
+            (Self::Map(key_a, a), Self::Map(key_b, b)) => {
+                key_a.to_string() == key_b.to_string() && a == b
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ValueType {
+    fn of(ty: &[TokenTree]) -> Self {
+        let mut depth = 0;
+        let mut name = None;
+        let mut args = Vec::new();
+
+        for token in ty {
+            match token {
+                TokenTree::Punct(p) if p.as_char() == '<' => {
+                    depth += 1;
+                    if depth == 1 {
+                        args.push(Vec::new());
+                        continue;
+                    }
+                }
+                TokenTree::Punct(p) if p.as_char() == '>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        continue;
+                    }
+                }
+                TokenTree::Punct(p) if p.as_char() == ',' && depth == 1 => {
+                    args.push(Vec::new());
+                    continue;
+                }
+                TokenTree::Ident(ident) if depth == 0 => name = Some(ident.to_string()),
+                _ => {}
+            }
+            if let Some(arg) = args.last_mut().filter(|_| depth > 0) {
+                arg.push(token.clone());
+            }
+        }
+
+        let arg = |i: usize| Box::new(Self::of(&args[i]));
+        match (name.as_deref(), args.len()) {
+            (Some("Option"), 1) => Self::Option(arg(0)),
+            (Some("Vec" | "VecDeque" | "LinkedList" | "BTreeSet" | "HashSet"), 1) => {
+                Self::List(arg(0))
+            }
+            (Some("HashMap" | "BTreeMap"), 2..) => {
+                Self::Map(args[0].iter().cloned().collect(), arg(1))
+            }
+            _ => Self::Plain(ty.iter().cloned().collect()),
+        }
+    }
+
+    /// Bounds required by the generated code, `bound` is the trait of the values
+    fn predicates(&self, bound: &TokenStream, predicates: &mut Vec<TokenStream>) {
+        match self {
+            Self::Plain(ty) if !ty.is_empty() => predicates.push(quote!(#ty: #bound)),
+            Self::Plain(_) => {}
+            Self::Option(inner) | Self::List(inner) => inner.predicates(bound, predicates),
+            Self::Map(key, inner) => {
+                predicates.push(quote!(#key: std::fmt::Display));
+                inner.predicates(bound, predicates);
+            }
+        }
+    }
+}
+
+struct NormalizedField {
+    name: String,
+    /// Identifier of a named field or index of a tuple field
+    member: TokenStream,
+    kind: FieldKind,
+    ty: ValueType,
+    /// Value used when an `Option` field is `None`
+    default: Option<String>,
+    /// Function converting the value of the field into a string
+    with: Option<TokenStream>,
+}
+
+impl NormalizedField {
+    /// Predicates of the where clause required to push the variables of the field
+    fn predicates(&self, predicates: &mut Vec<TokenStream>) {
+        // the function converting the value already constrains its type
+        if self.with.is_some() {
+            return;
+        }
+        let bound = match self.kind {
+            FieldKind::Value => quote!(std::string::ToString),
+            FieldKind::Nested | FieldKind::Flatten => quote!(srtemplate::Variable<'variable>),
+        };
+        self.ty.predicates(&bound, predicates);
+    }
+
+    /// Statements pushing the variables of the field, `value` is a reference to the field
+    fn push(&self, value: TokenStream) -> TokenStream {
+        let name = &self.name;
+
+        if let Some(with) = &self.with {
+            return quote! {
+                fields.push((#name.into(), #with(#value).to_string()));
+            };
+        }
+
+        match (&self.ty, &self.default) {
+            (ValueType::Option(inner), Some(default)) => {
+                let some = self.push_value(inner, quote!(#name), quote!(value), 0);
+                quote! {
+                    if let Some(value) = #value {
+                        #some
+                    } else {
+                        fields.push((#name.into(), #default.to_string()));
+                    }
+                }
+            }
+            (ty, _) => self.push_value(ty, quote!(#name), value, 0),
+        }
+    }
+
+    /// Statements pushing `value`, of type `ty`, with the name `name`.
+    /// `depth` is the number of collections containing the value, the variables of each
+    /// depth are suffixed to avoid shadowing the outer ones
+    fn push_value(
+        &self,
+        ty: &ValueType,
+        name: TokenStream,
+        value: TokenStream,
+        depth: usize,
+    ) -> TokenStream {
+        let item_name = format_ident!("name_{depth}");
+        let item = format_ident!("value_{depth}");
+        // names inside collections are built at runtime, so they are cloned for every variable
+        let shared_name = if depth > 0 {
+            quote!(#name.clone())
+        } else {
+            quote!(#name)
+        };
+
+        match ty {
+            ValueType::Option(inner) => {
+                let some = self.push_value(inner, name, quote!(#item), depth);
+                quote! {
+                    if let Some(#item) = #value {
+                        #some
+                    }
+                }
+            }
+            ValueType::List(inner) => {
+                let index = format_ident!("index_{depth}");
+                let push = self.push_value(inner, quote!(#item_name), quote!(#item), depth + 1);
+                quote! {
+                    for (#index, #item) in (#value).iter().enumerate() {
+                        let #item_name = format!("{}.{}", #name, #index);
+                        #push
+                    }
+                }
+            }
+            ValueType::Map(_, inner) => {
+                let key = format_ident!("key_{depth}");
+                let push = self.push_value(inner, quote!(#item_name), quote!(#item), depth + 1);
+                quote! {
+                    for (#key, #item) in (#value).iter() {
+                        let #item_name = format!("{}.{}", #name, #key);
+                        #push
+                    }
+                }
+            }
+            ValueType::Plain(_) => match self.kind {
+                FieldKind::Value => quote! {
+                    fields.push((#name.into(), (#value).to_string()));
+                },
+                FieldKind::Nested => quote! {
+                    fields.extend(
+                        srtemplate::Variable::fields(#value)
+                            .map(|(name, value)| (srtemplate::join_name(#shared_name, name), value)),
+                    );
+                },
+                // the value of the field itself keeps the name of the field
+                FieldKind::Flatten => quote! {
+                    fields.extend(
+                        srtemplate::Variable::fields(#value).map(|(name, value)| {
+                            (if name.is_empty() { #shared_name.into() } else { name }, value)
+                        }),
+                    );
+                },
+            },
+        }
+    }
+}
+
+/// Prefix of the variables of a struct or enum and the case of its fields, the prefix is
+/// empty with `#[template(prefix = false)]`
+fn container_names(
+    name_ident: &Ident,
+    attributes: &[Attribute],
+) -> Result<(String, Option<TextCase>), Error> {
     let mut name_struct = name_ident.to_string();
+    let mut prefix = true;
     let mut struct_case = None;
     let mut field_case = Some(TextCase::Snake);
 
-    for attr in struct_decl.attributes.iter() {
+    for attr in attributes.iter() {
         let attrs = parse_template_attribute(attr)?;
         for (key, value) in attrs {
             match key.as_str() {
@@ -79,127 +490,381 @@ fn derive_struct(struct_decl: &Struct) -> Result<TokenStream, Error> {
                     name_struct = value;
                 }
                 "case" => {
-                    struct_case = Some(TextCase::from_str(&value).map_err(|e| Error::new(e))?);
+                    struct_case = Some(TextCase::from_str(&value).map_err(Error::new)?);
                 }
                 "case_fields" => {
-                    field_case = Some(TextCase::from_str(&value).map_err(|e| Error::new(e))?);
+                    field_case = Some(TextCase::from_str(&value).map_err(Error::new)?);
+                }
+                "prefix" => {
+                    prefix = value.parse().map_err(|_| {
+                        Error::new(format!(
+                            "`prefix` must be `true` or `false`, found `{value}`"
+                        ))
+                    })?;
                 }
                 _ => {}
             }
         }
     }
 
-    let impl_generics = &struct_decl.generic_params;
-    let bounded_where_clause =
-        struct_decl.create_derive_where_clause(quote!(miniserde::Deserialize));
+    if !prefix {
+        return Ok((String::new(), field_case));
+    }
+    if name_struct.is_empty() {
+        name_struct = name_ident.to_string();
+    }
+    let prefix = struct_case
+        .map(|s| s.convert(&name_struct))
+        .unwrap_or(name_struct);
 
-    let fields = match &struct_decl.fields {
-        Fields::Named(fields) => {
-            let mut normalized_fields = Vec::new();
+    Ok((prefix, field_case))
+}
+
+/// Fields that are not ignored, with their names after applying their attributes
+fn named_fields(
+    fields: &Fields,
+    field_case: Option<TextCase>,
+) -> Result<Vec<NormalizedField>, Error> {
+    let mut normalized_fields = Vec::new();
 
+    match fields {
+        Fields::Unit => {}
+        Fields::Named(fields) => {
             for (field, _) in fields.fields.iter() {
-                let mut ignore = false;
-                let mut field_name = field.name.to_string();
-
-                for attr in field.attributes.iter() {
-                    let attrs = parse_template_attribute(attr)?;
-                    for (key, value) in attrs {
-                        match key.as_str() {
-                            "ignore" => {
-                                ignore = true;
-                            }
-                            "case" => {
-                                let case = TextCase::from_str(&value).map_err(|e| Error::new(e))?;
-                                field_name = case.convert(&field_name);
-                            }
-                            "rename" | "alias" => {
-                                field_name = value;
-                            }
-                            _ => {}
-                        }
-                    }
+                if let Some(name) =
+                    field_name(&field.attributes, field.name.to_string(), field_case)?
+                {
+                    let member = &field.name;
+                    normalized_fields.push(field_options(
+                        &field.attributes,
+                        name,
+                        quote!(#member),
+                        &field.ty.tokens,
+                    )?);
                 }
+            }
+        }
+        // tuple fields are named by index, so the case and renames do not apply
+        Fields::Tuple(tuple) => {
+            for (i, (field, _)) in tuple.fields.iter().enumerate() {
+                let i = proc_macro2::Literal::usize_unsuffixed(i);
+                normalized_fields.push(field_options(
+                    &field.attributes,
+                    i.to_string(),
+                    quote!(#i),
+                    &field.ty.tokens,
+                )?);
+            }
+        }
+    }
 
-                if !ignore {
-                    if let Some(case) = field_case {
-                        field_name = case.convert(&field_name);
-                    }
+    Ok(normalized_fields)
+}
 
-                    let name_ident = if name_struct.is_empty() {
-                        let name_ident = name_ident.to_string();
-                        struct_case
-                            .map(|s| s.convert(&name_ident))
-                            .unwrap_or(name_ident)
-                    } else {
-                        struct_case
-                            .map(|s| s.convert(&name_struct))
-                            .unwrap_or(name_struct.clone())
-                    };
-                    let name = format!("{name_ident}.{field_name}");
-                    let field = &field.name;
-
-                    normalized_fields.push(quote! {
-                        (
-                            #name.into(),
-                            self.#field.to_string()
-                        )
-                    });
-                }
-            }
-            normalized_fields
-        }
-        Fields::Unit => {
-            let name_ident = if name_struct.is_empty() {
-                let name_ident = name_ident.to_string();
-                struct_case
-                    .map(|s| s.convert(&name_ident))
-                    .unwrap_or(name_ident)
-            } else {
-                struct_case
-                    .map(|s| s.convert(&name_struct))
-                    .unwrap_or(name_struct)
+fn field_options(
+    attributes: &[Attribute],
+    name: String,
+    member: TokenStream,
+    ty: &[TokenTree],
+) -> Result<NormalizedField, Error> {
+    let mut field = NormalizedField {
+        name,
+        member,
+        kind: FieldKind::Value,
+        ty: ValueType::of(ty),
+        default: None,
+        with: None,
+    };
+
+    for attr in attributes.iter() {
+        for (key, value) in parse_template_attribute(attr)? {
+            let kind = match key.as_str() {
+                "nested" => FieldKind::Nested,
+                "flatten" => FieldKind::Flatten,
+                "default" => {
+                    field.default = Some(value);
+                    continue;
+                }
+                "with" => {
+                    let path = value.parse::<TokenStream>().map_err(|_| {
+                        Error::new(format!("`{value}` is not a valid path to a function"))
+                    })?;
+                    field.with = Some(path);
+                    continue;
+                }
+                _ => continue,
             };
+            if field.kind != FieldKind::Value && field.kind != kind {
+                return Err(Error::new("a field cannot be both `nested` and `flatten`"));
+            }
+            field.kind = kind;
+        }
+    }
 
-            vec![quote! {
-                (
-                    #name_ident.into(),
-                    self.to_string()
-                )
-            }]
+    if field.default.is_some() && !matches!(field.ty, ValueType::Option(_)) {
+        return Err(Error::new(format!(
+            "`default` is only supported on `Option` fields, but `{}` is not an `Option`",
+            field.name
+        )));
+    }
+
+    Ok(field)
+}
+
+/// Name of a field or variant after applying its attributes, `None` if it is ignored
+fn field_name(
+    attributes: &[Attribute],
+    mut name: String,
+    field_case: Option<TextCase>,
+) -> Result<Option<String>, Error> {
+    let mut ignore = false;
+
+    for attr in attributes.iter() {
+        let attrs = parse_template_attribute(attr)?;
+        for (key, value) in attrs {
+            match key.as_str() {
+                "ignore" => {
+                    ignore = true;
+                }
+                "case" => {
+                    let case = TextCase::from_str(&value).map_err(Error::new)?;
+                    name = case.convert(&name);
+                }
+                "rename" | "alias" => {
+                    name = value;
+                }
+                _ => {}
+            }
         }
-        Fields::Tuple(tuple) => tuple
-            .fields
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
-                let i = proc_macro2::Literal::usize_unsuffixed(i);
-                let name_ident = if name_struct.is_empty() {
-                    let name_ident = name_ident.to_string();
-                    struct_case
-                        .map(|s| s.convert(&name_ident))
-                        .unwrap_or(name_ident)
-                } else {
-                    struct_case
-                        .map(|s| s.convert(&name_struct))
-                        .unwrap_or(name_struct.clone())
-                };
-                let name = format!("{name_ident}.{i}");
-                quote! {
-                    (
-                        #name.into(),
-                        self.#i.to_string()
-                    )
+    }
+
+    if ignore {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        field_case.map_or(name.clone(), |case| case.convert(&name)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enum_variants() {
+        let tokens = derive(quote! {
+            #[template(case = "lower")]
+            enum Shape {
+                Empty,
+                #[template(rename = "round")]
+                Circle { radius: f32, #[template(ignore)] cached: f32 },
+                RectangleSize(u8, u8),
+                #[template(ignore)]
+                Hidden,
+            }
+        })
+        .unwrap()
+        .to_string();
+
+        let expected = [
+            quote!(srtemplate::join_name("shape", name)),
+            quote! {
+                Self::Empty { .. } => {
+                    fields.push(("".into(), "empty".to_string()));
                 }
-            })
-            .collect(),
-    };
+            },
+            quote! {
+                Self::Circle { radius: field_radius, .. } => {
+                    fields.push(("".into(), "round".to_string()));
+                    fields.push(("radius".into(), (field_radius).to_string()));
+                }
+            },
+            quote! {
+                Self::RectangleSize { 0: field_0, 1: field_1, .. } => {
+                    fields.push(("".into(), "rectangle_size".to_string()));
+                    fields.push(("0".into(), (field_0).to_string()));
+                    fields.push(("1".into(), (field_1).to_string()));
+                }
+            },
+            quote!(Self::Hidden { .. } => {}),
+        ];
 
-    Ok(quote! {
-        impl<'variable, #impl_generics> srtemplate::Variable<'variable> for #name_ident<#impl_generics>
-        #bounded_where_clause {
-            fn variables(&self) -> impl Iterator<Item = (std::borrow::Cow<'variable, str>, String)> {
-                [ #(#fields),* ].into_iter()
+        for tokens_part in expected {
+            assert!(
+                tokens.contains(&tokens_part.to_string()),
+                "{tokens_part} not in {tokens}"
+            );
+        }
+    }
+
+    #[test]
+    fn nested_and_flatten_fields() {
+        let tokens = derive(quote! {
+            #[template(case_fields = "camel")]
+            struct User {
+                #[template(nested)]
+                home_address: Address,
+                #[template(flatten)]
+                profile: Profile,
+            }
+        })
+        .unwrap()
+        .to_string();
+
+        let expected = [
+            quote! {
+                fields.extend(
+                    srtemplate::Variable::fields(&self.home_address)
+                        .map(|(name, value)| (srtemplate::join_name("homeAddress", name), value)),
+                );
+            },
+            quote! {
+                fields.extend(
+                    srtemplate::Variable::fields(&self.profile).map(|(name, value)| {
+                        (if name.is_empty() { "profile".into() } else { name }, value)
+                    }),
+                );
+            },
+        ];
+
+        for tokens_part in expected {
+            assert!(
+                tokens.contains(&tokens_part.to_string()),
+                "{tokens_part} not in {tokens}"
+            );
+        }
+        assert!(derive(quote! {
+            struct Both {
+                #[template(nested, flatten)]
+                inner: Inner,
             }
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn value_types() {
+        let of = |ty: TokenStream| ValueType::of(&ty.into_iter().collect::<Vec<_>>());
+        let plain = |ty: TokenStream| Box::new(ValueType::Plain(ty));
+
+        assert_eq!(of(quote!(String)), *plain(quote!(String)));
+        assert_eq!(of(quote!(Option<u8>)), ValueType::Option(plain(quote!(u8))));
+        assert_eq!(
+            of(quote!(std::vec::Vec<T>)),
+            ValueType::List(plain(quote!(T)))
+        );
+        assert_eq!(
+            of(quote!(HashMap<String, Vec<Option<u8>>>)),
+            ValueType::Map(
+                quote!(String),
+                Box::new(ValueType::List(Box::new(ValueType::Option(plain(quote!(
+                    u8
+                ))))))
+            )
+        );
+        assert_eq!(
+            of(quote!(Result<u8, String>)),
+            *plain(quote!(Result<u8, String>))
+        );
+        assert_eq!(of(quote!(&'a [u8])), *plain(quote!(&'a [u8])));
+    }
+
+    #[test]
+    fn optional_and_custom_fields() {
+        let tokens = derive(quote! {
+            struct Order {
+                #[template(default = "none")]
+                coupon: Option<String>,
+                #[template(with = "fmt::money")]
+                total: f32,
+            }
+        })
+        .unwrap()
+        .to_string();
+
+        let expected = [
+            quote! {
+                if let Some(value) = &self.coupon {
+                    fields.push(("coupon".into(), (value).to_string()));
+                } else {
+                    fields.push(("coupon".into(), "none".to_string()));
+                }
+            },
+            quote!(fields.push(("total".into(), fmt::money(&self.total).to_string()));),
+        ];
+
+        for tokens_part in expected {
+            assert!(
+                tokens.contains(&tokens_part.to_string()),
+                "{tokens_part} not in {tokens}"
+            );
         }
-    })
+        assert!(derive(quote! {
+            struct NotOptional {
+                #[template(default = "none")]
+                coupon: String,
+            }
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn generics() {
+        let tokens = derive(quote! {
+            struct Pair<'a, T: Clone = u8, U, const N: usize>
+            where
+                U: Copy,
+            {
+                name: &'a str,
+                values: Option<Vec<T>>,
+                #[template(nested)]
+                inner: U,
+                #[template(with = "len")]
+                cache: [T; N],
+            }
+        })
+        .unwrap()
+        .to_string();
+
+        let expected = quote! {
+            impl<'variable, 'a, T: Clone, U, const N: usize> srtemplate::Variable<'variable>
+                for Pair<'a, T, U, N,>
+            where
+                U: Copy,
+                T: std::string::ToString,
+                U: srtemplate::Variable<'variable>
+        };
+        assert!(
+            tokens.starts_with(&expected.to_string()),
+            "{expected} not in {tokens}"
+        );
+    }
+
+    #[test]
+    fn without_prefix() {
+        let tokens = derive(quote! {
+            #[template(prefix = false)]
+            struct Config { port: u16 }
+        })
+        .unwrap()
+        .to_string();
+        let expected =
+            quote!(srtemplate::Variable::fields(self).filter(|(name, _)| !name.is_empty()));
+
+        assert!(
+            tokens.contains(&expected.to_string()),
+            "{expected} not in {tokens}"
+        );
+        assert!(derive(quote! {
+            #[template(prefix = "no")]
+            struct Config { port: u16 }
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn unsupported_items() {
+        assert!(derive(quote!(union Bits { a: u8 })).is_err());
+    }
 }
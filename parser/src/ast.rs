@@ -0,0 +1,319 @@
+use std::ops::Range;
+
+/// Byte range of a node in the template
+pub type Span = Range<usize>;
+
+/// Variants of the types of nodes that exist in the syntax
+///
+/// Every node carries its [`Span`] in the template, for functions it covers
+/// from the name to the closing parenthesis and for strings it includes the quotes.
+/// The text of every node is a slice of the parsed template.
+///
+/// Every variant is matched by `srtemplate` to render the templates, so adding one
+/// is a breaking change of this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateNode<'a> {
+    /// Variables to be rendered
+    Variable(&'a str, Span),
+    /// Functions to be rendered
+    Function(&'a str, Vec<TemplateNode<'a>>, Span),
+    /// Plain text, pass as variable
+    String(&'a str, Span),
+    /// Number, pass as variable
+    Number(&'a str, Span),
+    /// Decimal, pass as variable
+    Float(&'a str, Span),
+    /// Plain text, this will be ignored in the rendering
+    RawText(&'a str, Span),
+}
+
+impl<'a> TemplateNode<'a> {
+    /// Byte range of the node in the template
+    pub fn span(&self) -> &Span {
+        match self {
+            TemplateNode::Variable(_, span)
+            | TemplateNode::Function(_, _, span)
+            | TemplateNode::String(_, span)
+            | TemplateNode::Number(_, span)
+            | TemplateNode::Float(_, span)
+            | TemplateNode::RawText(_, span) => span,
+        }
+    }
+
+    /// The name of the variable or function, or the text of the literal, without quotes
+    pub fn text(&self) -> &'a str {
+        match self {
+            TemplateNode::Variable(text, _)
+            | TemplateNode::Function(text, _, _)
+            | TemplateNode::String(text, _)
+            | TemplateNode::Number(text, _)
+            | TemplateNode::Float(text, _)
+            | TemplateNode::RawText(text, _) => text,
+        }
+    }
+}
+
+/// Traverses the nodes of a template
+///
+/// Every method has a default implementation, so implementors only override the nodes
+/// they are interested in. [`Visitor::visit_function`] visits the arguments by default,
+/// overriding it without calling [`walk_function`] skips them.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate_parser::{parse, Span, Visitor};
+///
+/// #[derive(Default)]
+/// struct Variables<'a>(Vec<&'a str>);
+///
+/// impl<'a> Visitor<'a> for Variables<'a> {
+///     fn visit_variable(&mut self, name: &'a str, _span: &Span) {
+///         self.0.push(name);
+///     }
+/// }
+///
+/// let nodes = parse("{{ a }} {{ trim(b, \"c\") }}", "{{", "}}").unwrap();
+/// let mut variables = Variables::default();
+/// variables.visit_nodes(&nodes);
+///
+/// assert_eq!(variables.0, ["a", "b"]);
+/// ```
+pub trait Visitor<'a> {
+    /// Visits every node of the slice
+    fn visit_nodes(&mut self, nodes: &[TemplateNode<'a>]) {
+        for node in nodes {
+            self.visit_node(node);
+        }
+    }
+
+    /// Visits a node, dispatching to the method of its variant
+    fn visit_node(&mut self, node: &TemplateNode<'a>) {
+        walk_node(self, node);
+    }
+
+    /// Visits a variable
+    fn visit_variable(&mut self, _name: &'a str, _span: &Span) {}
+
+    /// Visits a function call and, by default, its arguments
+    fn visit_function(&mut self, _name: &'a str, args: &[TemplateNode<'a>], _span: &Span) {
+        walk_function(self, args);
+    }
+
+    /// Visits a string literal, the value does not include the quotes
+    fn visit_string(&mut self, _value: &'a str, _span: &Span) {}
+
+    /// Visits a number literal
+    fn visit_number(&mut self, _value: &'a str, _span: &Span) {}
+
+    /// Visits a decimal literal
+    fn visit_float(&mut self, _value: &'a str, _span: &Span) {}
+
+    /// Visits the text outside of the delimiters
+    fn visit_raw_text(&mut self, _text: &'a str, _span: &Span) {}
+}
+
+/// Calls the method of `visitor` for the variant of `node`
+pub fn walk_node<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, node: &TemplateNode<'a>) {
+    match node {
+        TemplateNode::Variable(name, span) => visitor.visit_variable(name, span),
+        TemplateNode::Function(name, args, span) => visitor.visit_function(name, args, span),
+        TemplateNode::String(value, span) => visitor.visit_string(value, span),
+        TemplateNode::Number(value, span) => visitor.visit_number(value, span),
+        TemplateNode::Float(value, span) => visitor.visit_float(value, span),
+        TemplateNode::RawText(text, span) => visitor.visit_raw_text(text, span),
+    }
+}
+
+/// Visits the arguments of a function
+pub fn walk_function<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, args: &[TemplateNode<'a>]) {
+    visitor.visit_nodes(args);
+}
+
+/// Traverses the nodes of a template, allowing to modify them
+///
+/// It works like [`Visitor`], but every method receives the node mutably, so the nodes
+/// can be replaced, e.g. to rename variables or rewrite function calls.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate_parser::{parse, Span, TemplateNode, VisitorMut};
+///
+/// struct Rename;
+///
+/// impl<'a> VisitorMut<'a> for Rename {
+///     fn visit_variable_mut(&mut self, name: &mut &'a str, _span: &mut Span) {
+///         if *name == "user" {
+///             *name = "sender";
+///         }
+///     }
+/// }
+///
+/// let mut nodes = parse("{{ toLower(user) }}", "{{", "}}").unwrap();
+/// Rename.visit_nodes_mut(&mut nodes);
+///
+/// assert!(matches!(&nodes[0], TemplateNode::Function(_, args, _)
+///     if args[0] == TemplateNode::Variable("sender", 11..15)));
+/// ```
+pub trait VisitorMut<'a> {
+    /// Visits every node of the vector, nodes can be added or removed
+    fn visit_nodes_mut(&mut self, nodes: &mut Vec<TemplateNode<'a>>) {
+        for node in nodes.iter_mut() {
+            self.visit_node_mut(node);
+        }
+    }
+
+    /// Visits a node, dispatching to the method of its variant
+    fn visit_node_mut(&mut self, node: &mut TemplateNode<'a>) {
+        walk_node_mut(self, node);
+    }
+
+    /// Visits a variable
+    fn visit_variable_mut(&mut self, _name: &mut &'a str, _span: &mut Span) {}
+
+    /// Visits a function call and, by default, its arguments
+    fn visit_function_mut(
+        &mut self,
+        _name: &mut &'a str,
+        args: &mut Vec<TemplateNode<'a>>,
+        _span: &mut Span,
+    ) {
+        walk_function_mut(self, args);
+    }
+
+    /// Visits a string literal, the value does not include the quotes
+    fn visit_string_mut(&mut self, _value: &mut &'a str, _span: &mut Span) {}
+
+    /// Visits a number literal
+    fn visit_number_mut(&mut self, _value: &mut &'a str, _span: &mut Span) {}
+
+    /// Visits a decimal literal
+    fn visit_float_mut(&mut self, _value: &mut &'a str, _span: &mut Span) {}
+
+    /// Visits the text outside of the delimiters
+    fn visit_raw_text_mut(&mut self, _text: &mut &'a str, _span: &mut Span) {}
+}
+
+/// Calls the method of `visitor` for the variant of `node`
+pub fn walk_node_mut<'a, V: VisitorMut<'a> + ?Sized>(visitor: &mut V, node: &mut TemplateNode<'a>) {
+    match node {
+        TemplateNode::Variable(name, span) => visitor.visit_variable_mut(name, span),
+        TemplateNode::Function(name, args, span) => visitor.visit_function_mut(name, args, span),
+        TemplateNode::String(value, span) => visitor.visit_string_mut(value, span),
+        TemplateNode::Number(value, span) => visitor.visit_number_mut(value, span),
+        TemplateNode::Float(value, span) => visitor.visit_float_mut(value, span),
+        TemplateNode::RawText(text, span) => visitor.visit_raw_text_mut(text, span),
+    }
+}
+
+/// Visits the arguments of a function
+pub fn walk_function_mut<'a, V: VisitorMut<'a> + ?Sized>(
+    visitor: &mut V,
+    args: &mut Vec<TemplateNode<'a>>,
+) {
+    visitor.visit_nodes_mut(args);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[derive(Default)]
+    struct Kinds(Vec<&'static str>);
+
+    impl Visitor<'_> for Kinds {
+        fn visit_variable(&mut self, _name: &str, _span: &Span) {
+            self.0.push("variable");
+        }
+
+        fn visit_function(&mut self, _name: &str, args: &[TemplateNode<'_>], _span: &Span) {
+            self.0.push("function");
+            walk_function(self, args);
+        }
+
+        fn visit_string(&mut self, _value: &str, _span: &Span) {
+            self.0.push("string");
+        }
+
+        fn visit_number(&mut self, _value: &str, _span: &Span) {
+            self.0.push("number");
+        }
+
+        fn visit_float(&mut self, _value: &str, _span: &Span) {
+            self.0.push("float");
+        }
+
+        fn visit_raw_text(&mut self, _text: &str, _span: &Span) {
+            self.0.push("text");
+        }
+    }
+
+    #[test]
+    fn visit_in_order() {
+        let nodes = parse("Hi {{ f(a, g(\"s\"), 1, 2.5) }}!", "{{", "}}").unwrap();
+        let mut kinds = Kinds::default();
+        kinds.visit_nodes(&nodes);
+
+        assert_eq!(
+            kinds.0,
+            ["text", "function", "variable", "function", "string", "number", "float", "text"]
+        );
+    }
+
+    struct RemoveArguments;
+
+    impl<'a> VisitorMut<'a> for RemoveArguments {
+        fn visit_function_mut(
+            &mut self,
+            _name: &mut &'a str,
+            args: &mut Vec<TemplateNode<'a>>,
+            _span: &mut Span,
+        ) {
+            args.retain(|arg| !matches!(arg, TemplateNode::Number(..)));
+            walk_function_mut(self, args);
+        }
+    }
+
+    #[test]
+    fn modify_nodes() {
+        let mut nodes = parse("{{ f(1, g(2, x)) }}", "{{", "}}").unwrap();
+        RemoveArguments.visit_nodes_mut(&mut nodes);
+
+        assert_eq!(
+            nodes,
+            [TemplateNode::Function(
+                "f",
+                vec![TemplateNode::Function(
+                    "g",
+                    vec![TemplateNode::Variable("x", 13..14)],
+                    8..15
+                )],
+                3..16
+            )]
+        );
+    }
+
+    #[test]
+    fn node_accessors() {
+        let nodes = parse("{{ trim(\"a b\") }}", "{{", "}}").unwrap();
+        let TemplateNode::Function(_, args, _) = &nodes[0] else {
+            panic!("expected a function");
+        };
+
+        assert_eq!(nodes[0].text(), "trim");
+        assert_eq!(nodes[0].span(), &(3..14));
+        assert_eq!(args[0].text(), "a b");
+        assert_eq!(args[0].span(), &(8..13));
+    }
+
+    #[test]
+    fn custom_delimiters() {
+        assert_eq!(
+            parse("<% x %>", "<%", "%>").unwrap(),
+            [TemplateNode::Variable("x", 3..4)]
+        );
+        assert!(parse("<% x", "<%", "%>").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum SyntaxErrorToken {
+    #[error("end of input")]
+    Eof,
+
+    #[error("\"{0}\"")]
+    Char(char),
+
+    #[error("\"{0}\"")]
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum SyntaxErrorKind {
+    #[error("Expected {0}, but found {1}")]
+    Expected(SyntaxErrorToken, SyntaxErrorToken),
+
+    #[error("Invalid character in Number literal")]
+    InvalidNumber,
+
+    #[error("Unterminated function arguments")]
+    UnterminatedArgument,
+
+    #[error("Unterminated string literal")]
+    UnterminatedString,
+
+    #[error("Expected one '.' in a float")]
+    FloatDotted,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxError {
+    pub kind: SyntaxErrorKind,
+    pub at: usize,
+    pub context: String,
+    pub line: usize,
+    pub column: usize,
+    pub help: String,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.snippet().fmt(f)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+impl SyntaxErrorKind {
+    pub(crate) fn into_error(self, input: &str, at: usize) -> SyntaxError {
+        let (line, column, context) = get_line_from_offset(input, at);
+
+        SyntaxError {
+            kind: self,
+            at,
+            context,
+            line,
+            column,
+            help: String::new(),
+        }
+    }
+}
+
+impl SyntaxError {
+    /// Formats the error followed by the line of the template, pointing to where it happened
+    pub fn snippet(&self) -> Snippet<'_> {
+        Snippet {
+            title: "SyntaxError",
+            color: true,
+            message: &self.kind,
+            line: self.line,
+            column: self.column + 1,
+            len: 1,
+            context: &self.context,
+            help: &self.help,
+        }
+    }
+
+    pub(crate) fn found_eof(input: &str, at: usize, expected: impl Into<String>) -> Self {
+        let (line, column, context) = get_line_from_offset(input, at);
+
+        let expected = expected.into();
+        SyntaxError {
+            help: format!("help: add \"{expected}\""),
+            kind: SyntaxErrorKind::Expected(
+                SyntaxErrorToken::String(expected),
+                SyntaxErrorToken::Eof,
+            ),
+            at,
+            context,
+            line,
+            column,
+        }
+    }
+}
+
+fn get_line_from_offset(input: &str, mut offset: usize) -> (usize, usize, String) {
+    loop {
+        let last_newline = offset
+            - input[..offset]
+                .chars()
+                .rev()
+                .position(|c| c == '\n')
+                .unwrap_or(offset);
+
+        let line = input[..=last_newline.min(input.len() - 1)]
+            .chars()
+            .filter(|c| *c == '\n')
+            .count()
+            + 1;
+
+        let Some(column) = (offset - last_newline).checked_sub(1) else {
+            offset = last_newline.saturating_sub(1);
+            continue;
+        };
+
+        let next_newline = input[offset..]
+            .chars()
+            .skip(1)
+            .position(|c| c == '\n')
+            .unwrap_or_else(|| input.len() - offset)
+            + offset;
+
+        let context = input[last_newline..next_newline].to_owned();
+
+        break (line, column, context);
+    }
+}
+
+/// Error message pointing to the line of the template where it happened,
+/// shared by syntax and rendering errors
+#[doc(hidden)]
+pub struct Snippet<'a> {
+    pub title: &'a str,
+    pub color: bool,
+    pub message: &'a dyn fmt::Display,
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+    pub context: &'a str,
+    pub help: &'a str,
+}
+
+impl<'a> Snippet<'a> {
+    /// Error pointing to the `span` of the template `input`
+    pub fn at(input: &'a str, span: Range<usize>, message: &'a dyn fmt::Display) -> Self {
+        let line_start = input[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[span.start..]
+            .find('\n')
+            .map_or(input.len(), |i| span.start + i);
+
+        Self {
+            title: "Error",
+            color: true,
+            message,
+            line: input[..span.start].matches('\n').count() + 1,
+            column: input[line_start..span.start].chars().count() + 1,
+            len: input[span.start..span.end.min(line_end)].chars().count(),
+            context: &input[line_start..line_end],
+            help: "",
+        }
+    }
+
+    /// Enables or disables the ANSI colors
+    pub fn colored(self, color: bool) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Wraps `text` with the ANSI `style` if colors are enabled
+    fn paint<'t>(&self, style: &str, text: &'t str) -> Cow<'t, str> {
+        if self.color {
+            Cow::Owned(format!("\x1b[{style}m{text}\x1b[0m"))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+}
+
+impl fmt::Display for Snippet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_padding = " ".repeat(self.line.to_string().len());
+        let arrow_padding = " ".repeat(self.column.saturating_sub(1));
+        let mut underline = "^".repeat(self.len.max(1));
+        if !self.help.is_empty() {
+            underline = format!("{underline} {}", self.help);
+        }
+
+        let title = format!("{}:", self.title);
+        let gutter = format!(" {} | ", self.line);
+
+        writeln!(f, "{} {}", self.paint("1;91", &title), self.message)?;
+        writeln!(
+            f,
+            " {} {}:{}",
+            self.paint("1;34", "-->"),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{}{}", self.paint("1;34", &gutter), self.context)?;
+        writeln!(
+            f,
+            " {line_padding}   {arrow_padding}{}",
+            self.paint("1;91", &underline)
+        )
+    }
+}
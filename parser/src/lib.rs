@@ -0,0 +1,30 @@
+//! Parser and AST of the templates of [`srtemplate`](https://docs.rs/srtemplate).
+//!
+//! It is shared by `srtemplate`, which renders the templates at runtime, and its
+//! macros, which check and parse the templates at compile time.
+//!
+//! ## Example
+//! ```
+//! use srtemplate_parser::{parse, TemplateNode};
+//!
+//! let nodes = parse("Hi {{ toLower(name) }}", "{{", "}}").unwrap();
+//!
+//! assert_eq!(nodes[0], TemplateNode::RawText("Hi ", 0..3));
+//! assert!(matches!(&nodes[1], TemplateNode::Function("toLower", args, _) if args.len() == 1));
+//! ```
+
+/// The `ast` module exposes the nodes of a parsed template and visitors to traverse them.
+mod ast;
+
+/// The `error` module contains the syntax errors and their display.
+mod error;
+
+/// The `parser` module is responsible for parsing template strings into nodes.
+mod parser;
+
+pub use ast::{
+    walk_function, walk_function_mut, walk_node, walk_node_mut, Span, TemplateNode, Visitor,
+    VisitorMut,
+};
+pub use error::{Snippet, SyntaxError, SyntaxErrorKind, SyntaxErrorToken};
+pub use parser::{parse, parse_recovering};
@@ -0,0 +1,243 @@
+#[cfg(feature = "debug")]
+use log::trace;
+
+mod functions;
+mod literals;
+
+#[cfg(test)]
+mod test;
+
+use functions::parse_function_arguments;
+
+use crate::ast::TemplateNode;
+use crate::error::{SyntaxError, SyntaxErrorKind};
+
+/// Parses a template into its nodes, using `start` and `close` as delimiters of the expressions
+///
+/// # Arguments
+///
+/// * `input`: The template to parse.
+/// * `start`: The start delimiter, like `{{`.
+/// * `close`: The close delimiter, like `}}`.
+///
+/// # Returns
+///
+/// The nodes of the template in the order they appear.
+///
+/// # Errors
+///
+/// Returns the first [`SyntaxError`] found if the syntax of the template is invalid.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate_parser::{parse, TemplateNode};
+///
+/// let nodes = parse("Hi <% toLower(name) %>", "<%", "%>").unwrap();
+///
+/// assert_eq!(nodes[0], TemplateNode::RawText("Hi ", 0..3));
+/// assert!(matches!(&nodes[1], TemplateNode::Function("toLower", args, span)
+///     if args.len() == 1 && *span == (6..19)));
+/// ```
+pub fn parse<'a>(
+    input: &'a str,
+    start: &str,
+    close: &str,
+) -> Result<Vec<TemplateNode<'a>>, SyntaxError> {
+    #[cfg(feature = "debug")]
+    trace!("Start Parser: {input} with delimiters: {start} - {close}");
+    let mut res = Vec::with_capacity(20);
+    let chars = input.as_bytes();
+    let mut position = 0usize;
+
+    while !is_eof(chars, position) {
+        if advance_delimiter(chars, start, &mut position) {
+            res.push(parse_delimited_expression(
+                input,
+                chars,
+                close,
+                &mut position,
+            )?);
+            continue;
+        }
+
+        res.push(raw_text(input, chars, start, &mut position));
+    }
+
+    Ok(res)
+}
+
+/// Parses a template into its nodes like [`parse`], collecting every syntax error.
+///
+/// Unlike [`parse`], when an expression fails to parse the error is stored and the parser
+/// resynchronizes at the next delimiter: after the next close delimiter, or at the next start
+/// delimiter if it comes first. The broken expression produces no node.
+///
+/// # Returns
+///
+/// The nodes of every valid part of the template and the list of syntax errors found.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate_parser::parse_recovering;
+///
+/// let (nodes, errors) = parse_recovering("{{ a }}{{ trim( }}{{ f(1x) }}!", "{{", "}}");
+///
+/// assert_eq!(nodes.len(), 2);
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub fn parse_recovering<'a>(
+    input: &'a str,
+    start: &str,
+    close: &str,
+) -> (Vec<TemplateNode<'a>>, Vec<SyntaxError>) {
+    let mut res = Vec::with_capacity(20);
+    let mut errors = Vec::new();
+    let chars = input.as_bytes();
+    let mut position = 0usize;
+
+    while !is_eof(chars, position) {
+        if advance_delimiter(chars, start, &mut position) {
+            let expression_start = position;
+            match parse_delimited_expression(input, chars, close, &mut position) {
+                Ok(node) => res.push(node),
+                Err(e) => {
+                    errors.push(e);
+                    position = position.max(expression_start);
+                    synchronize(chars, start, close, &mut position);
+                }
+            }
+            continue;
+        }
+
+        res.push(raw_text(input, chars, start, &mut position));
+    }
+
+    (res, errors)
+}
+
+/// Parse an expression and its close delimiter, the start delimiter must be already consumed
+fn parse_delimited_expression<'a>(
+    input: &'a str,
+    chars: &[u8],
+    close: &str,
+    position: &mut usize,
+) -> Result<TemplateNode<'a>, SyntaxError> {
+    let var = parse_template_expression(input, chars, position)?;
+
+    // check end of sentence
+    if !advance_delimiter(chars, close, position) {
+        return Err(SyntaxError::found_eof(input, *position, close));
+    }
+
+    Ok(var)
+}
+
+/// Advance until after the next close delimiter, or until the next start delimiter
+fn synchronize(chars: &[u8], start: &str, close: &str, position: &mut usize) {
+    while !is_eof(chars, *position) {
+        if advance_delimiter(chars, close, position) || check_delimiter(chars, start, *position) {
+            return;
+        }
+        advance(chars, position);
+    }
+}
+
+fn parse_template_expression<'a>(
+    input: &'a str,
+    chars: &[u8],
+    position: &mut usize,
+) -> Result<TemplateNode<'a>, SyntaxError> {
+    skip_whitespace(chars, position);
+    // expect ident
+    let (start, name_end) = identifier(chars, position);
+    skip_whitespace(chars, position);
+
+    if !is_eof(chars, *position) && chars[*position] == b'(' {
+        advance(chars, position);
+        skip_whitespace(chars, position);
+
+        let args = parse_function_arguments(input, chars, position)?;
+        skip_whitespace(chars, position);
+
+        if !advance_delimiter(chars, ")", position) {
+            return Err(SyntaxErrorKind::UnterminatedArgument.into_error(input, *position));
+        }
+        let end = *position;
+        skip_whitespace(chars, position);
+
+        Ok(TemplateNode::Function(
+            &input[start..name_end],
+            args,
+            start..end,
+        ))
+    } else {
+        Ok(TemplateNode::Variable(
+            &input[start..name_end],
+            start..name_end,
+        ))
+    }
+}
+
+fn identifier(chars: &[u8], position: &mut usize) -> (usize, usize) {
+    let start = *position;
+    while !is_eof(chars, *position)
+        && (chars[*position].is_ascii_alphanumeric()
+            || chars[*position] == b'_'
+            || chars[*position] == b'.')
+    {
+        advance(chars, position);
+    }
+
+    (start, *position)
+}
+
+fn raw_text<'a>(
+    input: &'a str,
+    chars: &[u8],
+    open_delim: &str,
+    position: &mut usize,
+) -> TemplateNode<'a> {
+    let start = *position;
+    while !is_eof(chars, *position) {
+        if check_delimiter(chars, open_delim, *position) {
+            break;
+        }
+        advance(chars, position);
+    }
+
+    TemplateNode::RawText(&input[start..*position], start..*position)
+}
+
+fn advance(chars: &[u8], position: &mut usize) {
+    if !is_eof(chars, *position) {
+        *position += 1;
+    }
+}
+
+fn check_delimiter(chars: &[u8], delim: &str, position: usize) -> bool {
+    position + delim.len() <= chars.len()
+        && &chars[position..position + delim.len()] == delim.as_bytes()
+}
+
+fn advance_delimiter(chars: &[u8], delim: &str, position: &mut usize) -> bool {
+    if check_delimiter(chars, delim, *position) {
+        if *position + delim.len() <= chars.len() {
+            *position += delim.len();
+        }
+        return true;
+    }
+
+    false
+}
+
+fn is_eof(chars: &[u8], position: usize) -> bool {
+    position >= chars.len()
+}
+
+fn skip_whitespace(chars: &[u8], position: &mut usize) {
+    while !is_eof(chars, *position) && chars[*position].is_ascii_whitespace() {
+        advance(chars, position);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Error;
+use crate::error::SyntaxError;
 
 use super::literals::{number_literal, string_literal};
 use super::{advance_delimiter, is_eof, parse_template_expression, skip_whitespace, TemplateNode};
@@ -7,7 +7,7 @@ pub fn parse_function_arguments<'a>(
     input: &'a str,
     chars: &[u8],
     position: &mut usize,
-) -> Result<Vec<TemplateNode<'a>>, Error> {
+) -> Result<Vec<TemplateNode<'a>>, SyntaxError> {
     let mut args = Vec::new();
 
     while !is_eof(chars, *position) && chars[*position] != b')' {
@@ -1,12 +1,13 @@
-use crate::Error;
+use crate::error::SyntaxError;
 
-use super::{advance, is_eof, SyntaxErrorKind, TemplateNode};
+use super::{advance, is_eof, TemplateNode};
+use crate::error::SyntaxErrorKind;
 
 pub fn string_literal<'a>(
     input: &'a str,
     chars: &[u8],
     position: &mut usize,
-) -> Result<TemplateNode<'a>, Error> {
+) -> Result<TemplateNode<'a>, SyntaxError> {
     advance(chars, position);
     let start = *position;
     let mut is_scapped = false;
@@ -19,7 +20,10 @@ pub fn string_literal<'a>(
             is_scapped = true;
         } else if token == b'"' {
             advance(chars, position);
-            return Ok(TemplateNode::String(&input[start..*position - 1]));
+            return Ok(TemplateNode::String(
+                &input[start..*position - 1],
+                start - 1..*position,
+            ));
         }
         advance(chars, position);
     }
@@ -31,7 +35,7 @@ pub fn number_literal<'a>(
     input: &'a str,
     chars: &[u8],
     position: &mut usize,
-) -> Result<TemplateNode<'a>, Error> {
+) -> Result<TemplateNode<'a>, SyntaxError> {
     let mut is_float = false;
     let start = *position;
 
@@ -54,10 +58,16 @@ pub fn number_literal<'a>(
     }
 
     if is_float {
-        return Ok(TemplateNode::Float(&input[start..*position]));
+        return Ok(TemplateNode::Float(
+            &input[start..*position],
+            start..*position,
+        ));
     }
 
-    Ok(TemplateNode::Number(&input[start..*position]))
+    Ok(TemplateNode::Number(
+        &input[start..*position],
+        start..*position,
+    ))
 }
 
 #[cfg(test)]
@@ -77,7 +87,7 @@ mod tests {
 
         assert!(result.is_ok());
         let node = result.unwrap();
-        if let TemplateNode::String(value) = node {
+        if let TemplateNode::String(value, _) = node {
             assert_eq!(value, "hello world");
         } else {
             panic!("Expected a String node");
@@ -92,7 +102,7 @@ mod tests {
 
         assert!(result.is_ok());
         let node = result.unwrap();
-        if let TemplateNode::String(value) = node {
+        if let TemplateNode::String(value, _) = node {
             assert_eq!(value, r#"hello \"world\""#);
         } else {
             panic!("Expected a String node");
@@ -107,9 +117,10 @@ mod tests {
 
         assert!(result.is_err());
 
-        if let Error::BadSyntax(error) = result.unwrap_err() {
-            assert_eq!(error.kind, SyntaxErrorKind::UnterminatedString);
-        }
+        assert_eq!(
+            result.unwrap_err().kind,
+            SyntaxErrorKind::UnterminatedString
+        );
         assert_eq!(position, 12);
     }
 
@@ -120,7 +131,7 @@ mod tests {
 
         assert!(result.is_ok());
         let node = result.unwrap();
-        if let TemplateNode::Number(value) = node {
+        if let TemplateNode::Number(value, _) = node {
             assert_eq!(value, "12345");
         } else {
             panic!("Expected a Number node");
@@ -135,7 +146,7 @@ mod tests {
 
         assert!(result.is_ok());
         let node = result.unwrap();
-        if let TemplateNode::Float(value) = node {
+        if let TemplateNode::Float(value, _) = node {
             assert_eq!(value, "123.45");
         } else {
             panic!("Expected a Float node");
@@ -149,9 +160,7 @@ mod tests {
         let result = number_literal(input, &chars, &mut position);
 
         assert!(result.is_err());
-        if let Error::BadSyntax(error) = result.unwrap_err() {
-            assert_eq!(error.kind, SyntaxErrorKind::FloatDotted);
-        }
+        assert_eq!(result.unwrap_err().kind, SyntaxErrorKind::FloatDotted);
         assert_eq!(position, 6);
     }
 
@@ -161,9 +170,7 @@ mod tests {
         let result = number_literal(input, &chars, &mut position);
 
         assert!(result.is_err());
-        if let Error::BadSyntax(error) = result.unwrap_err() {
-            assert_eq!(error.kind, SyntaxErrorKind::InvalidNumber);
-        }
+        assert_eq!(result.unwrap_err().kind, SyntaxErrorKind::InvalidNumber);
         assert_eq!(position, 3);
     }
 }
@@ -0,0 +1,289 @@
+use super::*;
+
+#[test]
+fn not_template() {
+    let s = "Hello World!";
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_ok());
+    assert_eq!(res, Ok(vec![TemplateNode::RawText("Hello World!", 0..12)]));
+}
+
+#[test]
+fn valid_syntax() {
+    let s = "Hello {{ variable1 }} test {{ _var_ia_ble  }}";
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_ok());
+}
+
+#[test]
+fn invalid_syntax() {
+    let s = "Hello {{ variable1 }";
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn incomplete_syntax() {
+    let s = "Hello {{ variable1";
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn curl_in_text() {
+    let s = "Hello {} } {{ variable1";
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn invalid_syntax_simple() {
+    let s = "Hello { variable1 }";
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_ok());
+}
+
+#[test]
+fn function_syntax() {
+    let s = "Hello {{ toLowerCase(variable1) }}";
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_ok());
+}
+
+#[test]
+fn function_outside() {
+    let s = "Hello trim(var) {{ toLowerCase(variable1) }}";
+    let res = parse(s, "{{", "}}");
+
+    assert_eq!(
+        res,
+        Ok(vec![
+            TemplateNode::RawText("Hello trim(var) ", 0..16),
+            TemplateNode::Function(
+                "toLowerCase",
+                vec![TemplateNode::Variable("variable1", 31..40)],
+                19..41
+            )
+        ])
+    );
+}
+
+#[test]
+fn test_function_parser() {
+    let input = "{{ toLowerCase(trim(variable)) }}";
+    let result = parse(input, "{{", "}}");
+    assert_eq!(
+        result,
+        Ok(vec![TemplateNode::Function(
+            "toLowerCase",
+            vec![TemplateNode::Function(
+                "trim",
+                vec![TemplateNode::Variable("variable", 20..28)],
+                15..29
+            )],
+            3..30
+        )])
+    );
+}
+
+#[test]
+fn test_function_without_args() {
+    let input = "{{ toLowerCase() }}";
+    let result = parse(input, "{{", "}}");
+    assert_eq!(
+        result,
+        Ok(vec![TemplateNode::Function("toLowerCase", vec![], 3..16)])
+    );
+}
+
+#[test]
+fn test_function_multiple_param() {
+    let input = "{{ toLowerCase(variable1, trim(variable), add_u8(10, 15), variable2) }}";
+    let result = parse(input, "{{", "}}");
+    assert_eq!(
+        result,
+        Ok(vec![TemplateNode::Function(
+            "toLowerCase",
+            vec![
+                TemplateNode::Variable("variable1", 15..24),
+                TemplateNode::Function(
+                    "trim",
+                    vec![TemplateNode::Variable("variable", 31..39)],
+                    26..40
+                ),
+                TemplateNode::Function(
+                    "add_u8",
+                    vec![
+                        TemplateNode::Number("10", 49..51),
+                        TemplateNode::Number("15", 53..55)
+                    ],
+                    42..56
+                ),
+                TemplateNode::Variable("variable2", 58..67),
+            ],
+            3..68
+        )])
+    );
+}
+
+#[test]
+fn raw_text() {
+    let s = r#"Hello {{ "ThIs Is a EXAMPLE" }}"#;
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn inner_function_raw_text() {
+    let s = r#"Hello {{ toLowerCase("ThIs Is a EXAMPLE") }}"#;
+    let res = parse(s, "{{", "}}");
+
+    assert_eq!(
+        res,
+        Ok(vec![
+            TemplateNode::RawText("Hello ", 0..6),
+            TemplateNode::Function(
+                "toLowerCase",
+                vec![TemplateNode::String("ThIs Is a EXAMPLE", 21..40)],
+                9..41
+            ),
+        ])
+    );
+}
+
+#[test]
+fn numbers() {
+    let s = r#"Hello {{ test(14, 0.25, 00000, 00000.0) }}"#;
+    let res = parse(s, "{{", "}}");
+
+    assert_eq!(
+        res,
+        Ok(vec![
+            TemplateNode::RawText("Hello ", 0..6),
+            TemplateNode::Function(
+                "test",
+                vec![
+                    TemplateNode::Number("14", 14..16),
+                    TemplateNode::Float("0.25", 18..22),
+                    TemplateNode::Number("00000", 24..29),
+                    TemplateNode::Float("00000.0", 31..38),
+                ],
+                9..39
+            ),
+        ])
+    );
+}
+
+#[test]
+fn invalid_numbers() {
+    let s = r#"Hello {{ test(14.0.8) }}"#;
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_err());
+
+    let s = r#"Hello {{ test(14test) }}"#;
+    let res = parse(s, "{{", "}}");
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn recursive_function_syntax() {
+    let s = r#"Hello {{ toLowerCase(trim(split(variable1, "|"))) }}"#;
+    let res = parse(s, "{{", "}}");
+
+    assert_eq!(
+        res,
+        Ok(vec![
+            TemplateNode::RawText("Hello ", 0..6),
+            TemplateNode::Function(
+                "toLowerCase",
+                vec![TemplateNode::Function(
+                    "trim",
+                    vec![TemplateNode::Function(
+                        "split",
+                        vec![
+                            TemplateNode::Variable("variable1", 32..41),
+                            TemplateNode::String("|", 43..46)
+                        ],
+                        26..47
+                    )],
+                    21..48
+                )],
+                9..49
+            )
+        ])
+    );
+}
+
+#[test]
+fn test_parser() {
+    let input = "This is some text. {{ variable }} and {{ toLowerCase(trim(variable)) }}";
+    let result = parse(input, "{{", "}}");
+    assert_eq!(
+        result,
+        Ok(vec![
+            TemplateNode::RawText("This is some text. ", 0..19),
+            TemplateNode::Variable("variable", 22..30),
+            TemplateNode::RawText(" and ", 33..38),
+            TemplateNode::Function(
+                "toLowerCase",
+                vec![TemplateNode::Function(
+                    "trim",
+                    vec![TemplateNode::Variable("variable", 58..66)],
+                    53..67
+                )],
+                41..68
+            )
+        ])
+    );
+}
+
+#[test]
+fn recovering_parser() {
+    let s = "a {{ f(1x) }} b {{ var }} c {{ g( {{ other }} d {{ \"str\" }}";
+    let (nodes, errors) = parse_recovering(s, "{{", "}}");
+
+    assert_eq!(
+        nodes,
+        vec![
+            TemplateNode::RawText("a ", 0..2),
+            TemplateNode::RawText(" b ", 13..16),
+            TemplateNode::Variable("var", 19..22),
+            TemplateNode::RawText(" c ", 25..28),
+            TemplateNode::Variable("other", 37..42),
+            TemplateNode::RawText(" d ", 45..48),
+        ]
+    );
+
+    let kinds = errors.into_iter().map(|e| e.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            SyntaxErrorKind::InvalidNumber,
+            SyntaxErrorKind::UnterminatedArgument,
+            SyntaxErrorKind::Expected(
+                crate::error::SyntaxErrorToken::String("}}".to_owned()),
+                crate::error::SyntaxErrorToken::Eof
+            ),
+        ]
+    );
+}
+
+#[test]
+fn recovering_parser_valid() {
+    let s = "Hello {{ toLower(var) }}!";
+
+    assert_eq!(
+        parse_recovering(s, "{{", "}}"),
+        (parse(s, "{{", "}}").unwrap(), vec![])
+    );
+}
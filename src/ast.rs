@@ -0,0 +1,113 @@
+pub use srtemplate_parser::{
+    walk_function, walk_function_mut, walk_node, walk_node_mut, Span, SyntaxError, SyntaxErrorKind,
+    TemplateNode, Visitor, VisitorMut,
+};
+
+use crate::error::Error;
+use crate::parser::{parser, parser_recovering};
+
+/// Parses a template into its nodes, using `start` and `close` as delimiters of the expressions
+///
+/// # Arguments
+///
+/// * `input`: The template to parse.
+/// * `start`: The start delimiter, like `{{`.
+/// * `close`: The close delimiter, like `}}`.
+///
+/// # Returns
+///
+/// The nodes of the template in the order they appear.
+///
+/// # Errors
+///
+/// Returns [`Error::BadSyntax`] if the syntax of the template is invalid.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate::ast::{parse, TemplateNode};
+///
+/// let nodes = parse("Hi <% toLower(name) %>", "<%", "%>").unwrap();
+///
+/// assert_eq!(nodes[0], TemplateNode::RawText("Hi ", 0..3));
+/// assert!(matches!(&nodes[1], TemplateNode::Function("toLower", args, span)
+///     if args.len() == 1 && *span == (6..19)));
+/// ```
+pub fn parse<'a>(input: &'a str, start: &str, close: &str) -> Result<Vec<TemplateNode<'a>>, Error> {
+    parser(input, start, close)
+}
+
+/// Parses a template like [`parse`], but instead of stopping at the first syntax error
+/// every error is collected and the parser keeps going after the expression that failed
+///
+/// # Arguments
+///
+/// * `input`: The template to parse.
+/// * `start`: The start delimiter, like `{{`.
+/// * `close`: The close delimiter, like `}}`.
+///
+/// # Returns
+///
+/// The nodes that could be parsed and an [`Error::BadSyntax`] for each syntax error,
+/// both in the order they appear.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate::ast::{parse_recovering, TemplateNode};
+///
+/// let (nodes, errors) = parse_recovering("{{ a }}{{ trim( }}{{ f(1x) }}!", "{{", "}}");
+///
+/// assert_eq!(nodes[0], TemplateNode::Variable("a", 3..4));
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub fn parse_recovering<'a>(
+    input: &'a str,
+    start: &str,
+    close: &str,
+) -> (Vec<TemplateNode<'a>>, Vec<Error>) {
+    parser_recovering(input, start, close)
+}
+
+/// A template parsed once, to be rendered many times with
+/// [`SrTemplate::render_parsed`](crate::SrTemplate::render_parsed)
+///
+/// It can be parsed at runtime with [`ParsedTemplate::parse`], or at compile time with
+/// the `template!` and `include_template!` macros of the `macros` feature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedTemplate<'a> {
+    source: &'a str,
+    nodes: Vec<TemplateNode<'a>>,
+}
+
+impl<'a> ParsedTemplate<'a> {
+    /// Parses the template, using `start` and `close` as delimiters of the expressions
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BadSyntax`] if the syntax of the template is invalid.
+    pub fn parse(source: &'a str, start: &str, close: &str) -> Result<Self, Error> {
+        Ok(Self {
+            source,
+            nodes: parse(source, start, close)?,
+        })
+    }
+
+    /// Builds the template from nodes already parsed, used by the `template!` macro
+    ///
+    /// The span of every node must be a range of `source`.
+    #[doc(hidden)]
+    pub fn from_parts(source: &'a str, nodes: Vec<TemplateNode<'a>>) -> Self {
+        Self { source, nodes }
+    }
+
+    /// The text of the template
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// The nodes of the template in the order they appear
+    pub fn nodes(&self) -> &[TemplateNode<'a>] {
+        &self.nodes
+    }
+}
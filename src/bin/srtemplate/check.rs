@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+
+use srtemplate::{Error, ReferenceKind, SrTemplate};
+
+use crate::render::read_template;
+
+/// Checks every file and prints all the errors found as `file:line:column: message`
+pub fn check(ctx: &SrTemplate, files: &[PathBuf]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors = 0;
+
+    for file in files {
+        let name = file.display();
+        let template = match read_template(Some(file)) {
+            Ok(template) => template,
+            Err(e) => {
+                eprintln!("{e}");
+                errors += 1;
+                continue;
+            }
+        };
+
+        let (refs, syntax_errors) = ctx.references_recovering(&template);
+        // the location of the diagnostic is 1-based like the one of the references
+        let mut found = syntax_errors
+            .iter()
+            .map(Error::diagnostic)
+            .filter_map(|d| Some((d.offset?, d.line?, d.column?, d.message)))
+            .chain(
+                refs.iter()
+                    .filter(|r| {
+                        r.kind == ReferenceKind::Function
+                            && !ctx.contains_function(r.name.to_owned())
+                    })
+                    .map(|r| {
+                        let message = format!("Function not implemented: {}", r.name);
+                        (r.span.start, r.line, r.column, message)
+                    }),
+            )
+            .collect::<Vec<_>>();
+
+        // list the errors of the file in the order they appear
+        found.sort_by_key(|(at, ..)| *at);
+        for (_, line, column, message) in &found {
+            eprintln!("{name}:{line}:{column}: {message}");
+        }
+        errors += found.len();
+    }
+
+    if errors > 0 {
+        return Err(format!("Found {errors} error(s) in {} file(s)", files.len()).into());
+    }
+
+    Ok(())
+}
+
+/// Prints the variables and functions referenced by the template, once each
+pub fn vars(ctx: &SrTemplate, file: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let template = read_template(Some(file))?;
+
+    for name in ctx.referenced_variables(&template)? {
+        println!("variable {name}");
+    }
+    for name in ctx.referenced_functions(&template)? {
+        println!("function {name}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! Command line interface for `srtemplate`.
+//!
+//! Renders a template file (or stdin) using variables from the command line,
+//! variable files and the process environment, and lints templates for CI.
+//!
+//! ```sh
+//! srtemplate page.html --var title=Home --vars config.json -o out.html
+//! srtemplate check templates/*.html
+//! srtemplate vars page.html
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use srtemplate::{ColorChoice, SrTemplate};
+
+mod check;
+mod render;
+
+/// Render srtemplate templates from the command line
+#[derive(Debug, Parser)]
+#[command(
+    name = "srtemplate",
+    version,
+    about,
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    render: render::RenderArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Checks the syntax of the templates and that every function they call is registered
+    Check {
+        /// Template files to check
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        #[command(flatten)]
+        delimiters: Delimiters,
+    },
+    /// Lists the variables and functions referenced by a template
+    Vars {
+        /// Template file to inspect, reads from stdin if it is `-`
+        file: PathBuf,
+
+        #[command(flatten)]
+        delimiters: Delimiters,
+    },
+}
+
+/// Delimiters of the template expressions
+#[derive(Debug, Args)]
+struct Delimiters {
+    /// Start delimiter of the template expressions
+    #[arg(long, default_value = "{{")]
+    start: String,
+
+    /// Close delimiter of the template expressions
+    #[arg(long, default_value = "}}")]
+    close: String,
+}
+
+impl Delimiters {
+    fn context(self) -> SrTemplate<'static> {
+        SrTemplate::with_delimiter(self.start, self.close)
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let res = match cli.command {
+        Some(Command::Check { files, delimiters }) => check::check(&delimiters.context(), &files),
+        Some(Command::Vars { file, delimiters }) => check::vars(&delimiters.context(), &file),
+        None => render::render(cli.render),
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            match e.downcast_ref::<srtemplate::Error>() {
+                Some(e) => eprint!("{}", e.display(ColorChoice::Auto)),
+                None => eprintln!("{e}"),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use srtemplate::{Error, SrTemplate};
+
+use crate::Delimiters;
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+    /// Template file to render, reads from stdin if it is not set or is `-`
+    template: Option<PathBuf>,
+
+    /// Adds a variable, can be used multiple times
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+    vars: Vec<(String, String)>,
+
+    /// Loads variables from a file (`.json`, `.env`, and `.toml` or `.yaml` when enabled),
+    /// can be used multiple times
+    #[arg(long = "vars", value_name = "FILE")]
+    var_files: Vec<PathBuf>,
+
+    /// Do not add the variables of the process environment
+    #[arg(long)]
+    no_env: bool,
+
+    #[command(flatten)]
+    delimiters: Delimiters,
+
+    /// Writes the rendered template to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+pub fn render(args: RenderArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let ctx = args.delimiters.context();
+
+    // Variables are added from the lowest to the highest precedence
+    if !args.no_env {
+        std::env::vars().for_each(|(name, value)| ctx.add_variable(name, value));
+    }
+    for file in &args.var_files {
+        load_variables(&ctx, file)?;
+    }
+    for (name, value) in args.vars {
+        ctx.add_variable(name, value);
+    }
+
+    let template = read_template(args.template.as_deref())?;
+    let rendered = ctx.render(template)?;
+
+    match args.output {
+        Some(path) => std::fs::write(&path, rendered)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))?,
+        None => io::stdout().write_all(rendered.as_bytes())?,
+    }
+
+    Ok(())
+}
+
+pub fn read_template(path: Option<&Path>) -> Result<String, String> {
+    match path {
+        Some(path) if path != Path::new("-") => std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display())),
+        _ => {
+            let mut template = String::new();
+            io::stdin()
+                .read_to_string(&mut template)
+                .map_err(|e| format!("Failed to read stdin: {e}"))?;
+            Ok(template)
+        }
+    }
+}
+
+fn load_variables(ctx: &SrTemplate, path: &Path) -> Result<(), Error> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    let is_dotenv = path
+        .file_name()
+        .and_then(|f| f.to_str())
+        .is_some_and(|f| f.starts_with(".env"));
+
+    match extension {
+        "json" => ctx.load_variables_json(path, ""),
+        #[cfg(feature = "toml")]
+        "toml" => ctx.load_variables_toml(path, ""),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => ctx.load_variables_yaml(path, ""),
+        _ if is_dotenv || extension == "env" => ctx.load_variables_dotenv(path, ""),
+        _ => Err(Error::LoadVariables {
+            file: path.display().to_string(),
+            key: None,
+            message: format!("unsupported variables file extension \"{extension}\""),
+        }),
+    }
+}
+
+fn parse_var(var: &str) -> Result<(String, String), String> {
+    var.split_once('=')
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("invalid variable \"{var}\", expected KEY=VALUE"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_var;
+
+    #[test]
+    fn var_argument() {
+        assert_eq!(
+            parse_var("user.name=Sergio=R"),
+            Ok(("user.name".to_owned(), "Sergio=R".to_owned()))
+        );
+        assert!(parse_var("=value").is_err());
+        assert!(parse_var("name").is_err());
+    }
+}
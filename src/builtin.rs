@@ -1,12 +1,12 @@
 #[cfg_attr(docsrs, doc(cfg(feature = "math")))]
 #[cfg(feature = "math")]
-pub(crate) mod math;
+pub mod math;
 #[cfg_attr(docsrs, doc(cfg(feature = "os")))]
 #[cfg(feature = "os")]
-pub(crate) mod os;
+pub mod os;
 #[cfg_attr(docsrs, doc(cfg(feature = "text")))]
 #[cfg(feature = "text")]
-pub(crate) mod text;
+pub mod text;
 
 #[cfg(feature = "typed_args")]
 use crate::helper::serialize::FromArgs;
@@ -42,7 +42,7 @@ use crate::prelude::FunctionError;
 /// impl FromArgs for MyArgs {
 ///     fn from_args(args: &[String]) -> FromArgsResult<Self> {
 ///         if args.len() != 2 {
-///             return Err(srtemplate::prelude::FromArgsError::ArgumentsIncomplete(args.len(), 2));
+///             return Err(srtemplate::prelude::FromArgsError::ArgumentNotExists("count".to_string(), args.len()));
 ///         }
 ///         let name = args[0].clone();
 ///         let count = args[1].parse::<u8>().map_err(|_| srtemplate::prelude::FromArgsError::BadType(args[1].clone()))?;
@@ -103,11 +103,30 @@ macro_rules! gen_math_use {
     ($tmp: ident, $name: ident, $( $t: ty ),* ) => {
         $(
             paste! {
-                $tmp.add_function(stringify!([<$name _ $t>]), builtin::math::[<$name _ $t>]);
+                $tmp.add_function_with_signature(
+                    stringify!([<$name _ $t>]),
+                    builtin::math::[<$name _ $t>],
+                    $crate::gen_math_use!(@signature [<$name _ $t>], $name, $t),
+                );
             }
         )*
     };
     (@$tmp: ident, $name: ident) => {
-        $tmp.add_function(stringify!($name), builtin::math::$name);
+        $tmp.add_function_with_signature(
+            stringify!($name),
+            builtin::math::$name,
+            $crate::gen_math_use!(@signature $name, $name, i32),
+        );
+    };
+    (@signature $func: ident, $name: ident, $t: ty) => {
+        $crate::prelude::FunctionSignature::new(stringify!($func))
+            .variadic_param("values", stringify!($t))
+            .description(concat!(
+                "Applies `",
+                stringify!($name),
+                "` to the values parsed as `",
+                stringify!($t),
+                "`"
+            ))
     };
 }
@@ -0,0 +1,133 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use crate::error::{Error, Location};
+use crate::template::function::FuncResult;
+use crate::SrTemplate;
+
+/// Source of the variables of templates compiled with the `compile_template!` macro
+///
+/// # Example
+///
+/// ```
+/// use std::borrow::Cow;
+/// use srtemplate::Variables;
+///
+/// struct Env;
+///
+/// impl Variables for Env {
+///     fn get(&self, name: &str) -> Option<Cow<'_, str>> {
+///         std::env::var(name).ok().map(Cow::Owned)
+///     }
+/// }
+/// ```
+pub trait Variables {
+    /// Returns the value of the variable `name`, or `None` if it does not exist
+    fn get(&self, name: &str) -> Option<Cow<'_, str>>;
+}
+
+impl<T: Variables + ?Sized> Variables for &T {
+    fn get(&self, name: &str) -> Option<Cow<'_, str>> {
+        T::get(self, name)
+    }
+}
+
+impl Variables for SrTemplate<'_> {
+    fn get(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.variables
+            .get(name)
+            .map(|value| Cow::Owned(value.to_owned()))
+    }
+}
+
+impl<K, V, S> Variables for HashMap<K, V, S>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn get(&self, name: &str) -> Option<Cow<'_, str>> {
+        HashMap::get(self, name).map(|value| Cow::Borrowed(value.as_ref()))
+    }
+}
+
+impl<K, V> Variables for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: AsRef<str>,
+{
+    fn get(&self, name: &str) -> Option<Cow<'_, str>> {
+        BTreeMap::get(self, name).map(|value| Cow::Borrowed(value.as_ref()))
+    }
+}
+
+/// Context with only the builtin functions, built once and shared by the `Display`
+/// implementations generated by `#[derive(Template)]`
+#[doc(hidden)]
+pub fn default_context() -> &'static SrTemplate<'static> {
+    static CONTEXT: OnceLock<SrTemplate<'static>> = OnceLock::new();
+    CONTEXT.get_or_init(SrTemplate::default)
+}
+
+/// Gets a variable for a compiled template, `span` is the location of the variable in `source`
+#[doc(hidden)]
+pub fn variable<'v, V: Variables + ?Sized>(
+    vars: &'v V,
+    name: &str,
+    source: &str,
+    span: Range<usize>,
+) -> Result<Cow<'v, str>, Error> {
+    vars.get(name)
+        .ok_or_else(|| Error::VariableNotFound(name.to_owned(), Location::new(source, span)))
+}
+
+/// Locates the error of a function called by a compiled template
+#[doc(hidden)]
+pub fn function(result: FuncResult, source: &str, span: Range<usize>) -> Result<String, Error> {
+    result.map_err(|e| Error::Function(e, Location::new(source, span)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn variables_sources() {
+        let ctx = SrTemplate::default();
+        ctx.add_variable("name", "World");
+        let map = HashMap::from([("name", "Map")]);
+        let tree = BTreeMap::from([("name".to_owned(), "Tree".to_owned())]);
+
+        assert_eq!(Variables::get(&ctx, "name").as_deref(), Some("World"));
+        assert_eq!(Variables::get(&map, "name").as_deref(), Some("Map"));
+        assert_eq!(Variables::get(&&tree, "name").as_deref(), Some("Tree"));
+        assert_eq!(Variables::get(&ctx, "missing"), None);
+    }
+
+    #[test]
+    fn locate_errors() {
+        let source = "Hi {{ name }}";
+        let map = HashMap::<String, String>::new();
+
+        assert_eq!(
+            variable(&map, "name", source, 6..10),
+            Err(Error::VariableNotFound(
+                "name".to_owned(),
+                Location::new(source, 6..10)
+            ))
+        );
+        assert!(matches!(
+            function(
+                Err(crate::prelude::FunctionError::ArgumentsIncomplete(1, 0)),
+                source,
+                3..13
+            ),
+            Err(Error::Function(_, location)) if location.column == 4
+        ));
+    }
+}
@@ -1,5 +1,16 @@
+use std::fmt;
+use std::ops::Range;
+
 use thiserror::Error;
 
+pub(crate) use srtemplate_parser::Snippet;
+
+mod diagnostic;
+mod kind;
+
+pub use diagnostic::{ColorChoice, Diagnostic, ErrorDisplay};
+pub use kind::ErrorKind;
+
 /// Collection of errors in the library
 #[derive(Clone, Debug, Error, PartialEq)]
 pub enum Error {
@@ -8,14 +19,210 @@ pub enum Error {
     BadSyntax(crate::parser::SyntaxError),
 
     /// This error appears when the variable to be rendered does not exist.
-    #[error("Variable not found: {0}")]
-    VariableNotFound(String),
+    #[error("{}", .1.snippet(&format_args!("Variable not found: {}", .0)))]
+    VariableNotFound(String, Location),
 
     /// This error appears when the function to be rendered does not exist.
-    #[error("Function not implemented: {0}")]
-    FunctionNotImplemented(String),
+    #[error("{}", .1.snippet(&format_args!("Function not implemented: {}", .0)))]
+    FunctionNotImplemented(String, Location),
 
     /// This error appears when the function to be rendered has suffered from an internal error.
-    #[error("Error Processing Function: {0}")]
-    Function(#[from] super::template::function::Error),
+    #[error("{}", .1.snippet(&format_args!("Error Processing Function: {}", .0)))]
+    Function(super::template::function::Error, Location),
+
+    /// This error appears when a value cannot be converted into variables.
+    #[error("Error serializing variables: {0}")]
+    Serialize(String),
+
+    /// This error appears when variables cannot be loaded from a file or string.
+    #[error(
+        "Failed to load variables from {file}{}: {message}",
+        .key.as_ref().map(|k| format!(" at key \"{k}\"")).unwrap_or_default()
+    )]
+    LoadVariables {
+        /// The path of the file, or `<string>` if the variables were loaded from a string
+        file: String,
+        /// The key that failed, if the error is not related to the whole file
+        key: Option<String>,
+        /// The reason of the failure
+        message: String,
+    },
+
+    /// This error appears when the output of a compiled template cannot be written.
+    #[error("Error writing the rendered template")]
+    Write(#[from] fmt::Error),
+}
+
+/// Position in the template of the node that caused an error
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    /// Byte offset in the template
+    pub at: usize,
+    /// Line, starting at 1
+    pub line: usize,
+    /// Column, starting at 1
+    pub column: usize,
+    /// Length in characters of the node, limited to the end of its line
+    pub len: usize,
+    /// The line of the template that contains the node
+    pub context: String,
+    /// Hint to fix the error, like the registered names similar to the one not found
+    pub help: String,
+}
+
+impl Location {
+    pub(crate) fn new(input: &str, span: Range<usize>) -> Self {
+        let at = floor_char_boundary(input, span.start);
+        let line_start = input[..at].rfind('\n').map_or(0, |n| n + 1);
+        let line_end = input[at..].find('\n').map_or(input.len(), |n| at + n);
+        let end = floor_char_boundary(input, span.end.clamp(at, line_end));
+
+        Self {
+            at,
+            line: input[..at].matches('\n').count() + 1,
+            column: input[line_start..at].chars().count() + 1,
+            len: input[at..end].chars().count().max(1),
+            context: input[line_start..line_end].to_owned(),
+            help: String::new(),
+        }
+    }
+
+    /// Location of the `name` of a node, which is at the start of its `span`
+    pub(crate) fn of_name(input: &str, name: &str, span: &Range<usize>) -> Self {
+        Self::new(input, span.start..span.end.min(span.start + name.len()))
+    }
+
+    /// Fills the help with the `candidates` that are similar to `name`, if any
+    pub(crate) fn suggest(
+        mut self,
+        name: &str,
+        candidates: impl IntoIterator<Item = String>,
+    ) -> Self {
+        let similar = similar_names(name, candidates);
+
+        self.help = match similar.as_slice() {
+            [] => String::new(),
+            [one] => format!("help: did you mean \"{one}\"?"),
+            many => format!("help: did you mean one of \"{}\"?", many.join("\", \"")),
+        };
+        self
+    }
+
+    /// Formats `message` followed by the line of the template, underlining the node
+    pub(crate) fn snippet<'a>(&'a self, message: &'a dyn fmt::Display) -> Snippet<'a> {
+        Snippet {
+            title: "Error",
+            color: true,
+            message,
+            line: self.line,
+            column: self.column,
+            len: self.len,
+            context: &self.context,
+            help: &self.help,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Largest char boundary of `input` that is not after `index`
+fn floor_char_boundary(input: &str, index: usize) -> usize {
+    let mut index = index.min(input.len());
+    while !input.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Maximum number of names suggested for a single error
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Returns the `candidates` closest to `name`
+///
+/// A candidate is similar if it only differs in case, or if its edit distance
+/// is at most a third of the length of `name`, rounded up.
+/// The closest candidates go first, case-insensitive matches before any other.
+fn similar_names(name: &str, candidates: impl IntoIterator<Item = String>) -> Vec<String> {
+    let lowercase = name.to_lowercase();
+    let max_distance = name.chars().count().div_ceil(3);
+
+    let mut similar = candidates
+        .into_iter()
+        .filter(|candidate| candidate != name)
+        .filter_map(|candidate| {
+            let distance = if candidate.to_lowercase() == lowercase {
+                0
+            } else {
+                edit_distance(name, &candidate)
+            };
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .collect::<Vec<_>>();
+
+    similar.sort();
+    similar
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("toLower", "toLower"), 0);
+        assert_eq!(edit_distance("nme", "name"), 1);
+    }
+
+    #[test]
+    fn case_insensitive_first() {
+        let similar = similar_names("tolower", names(&["env", "tolowr", "toLower", "trim"]));
+
+        assert_eq!(similar, names(&["toLower", "tolowr"]));
+    }
+
+    #[test]
+    fn no_similar_names() {
+        assert!(similar_names("user", names(&["env", "trim", "add_u8"])).is_empty());
+    }
+
+    #[test]
+    fn suggestion_help() {
+        let location = Location::new("{{ toLowercase(x) }}", 3..14)
+            .suggest("toLowercase", names(&["toLower", "toUpper"]));
+
+        assert_eq!(location.help, "help: did you mean \"toLower\"?");
+    }
 }
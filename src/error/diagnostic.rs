@@ -0,0 +1,221 @@
+use std::fmt;
+use std::io::IsTerminal;
+
+use super::{Error, ErrorKind};
+
+/// When to use ANSI colors to display an error
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Use colors if stderr is a terminal and the `NO_COLOR` environment variable is not set
+    #[default]
+    Auto,
+    /// Always use colors, like the [`Display`](fmt::Display) implementation of [`Error`]
+    Always,
+    /// Never use colors
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns whether the colors must be used
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                !matches!(std::env::var_os("NO_COLOR"), Some(v) if !v.is_empty())
+                    && std::io::stderr().is_terminal()
+            }
+        }
+    }
+}
+
+/// Displays an [`Error`] with or without colors, returned by [`Error::display`]
+pub struct ErrorDisplay<'a> {
+    error: &'a Error,
+    color: bool,
+}
+
+impl fmt::Display for ErrorDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = self.error.message();
+
+        match self.error {
+            Error::BadSyntax(e) => e.snippet().colored(self.color).fmt(f),
+            Error::VariableNotFound(_, location)
+            | Error::FunctionNotImplemented(_, location)
+            | Error::Function(_, location) => location.snippet(&message).colored(self.color).fmt(f),
+            _ => f.write_str(&message),
+        }
+    }
+}
+
+/// Machine readable representation of an [`Error`], for editors and CI annotations
+///
+/// With the `serde` feature it can be serialized, e.g. to JSON with the `json` feature.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate::SrTemplate;
+///
+/// let ctx = SrTemplate::default();
+/// let diagnostic = ctx.render("Hi {{ name }}").unwrap_err().diagnostic();
+///
+/// assert_eq!(diagnostic.message, "Variable not found: name");
+/// assert_eq!((diagnostic.line, diagnostic.column), (Some(1), Some(7)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnostic {
+    /// Stable code of the error, like `E0201`
+    pub code: &'static str,
+    /// Kind of the error, serialized as its name like `VariableNotFound`
+    pub kind: ErrorKind,
+    /// Description of the error, without location nor colors
+    pub message: String,
+    /// Byte offset in the template
+    pub offset: Option<usize>,
+    /// Line in the template, starting at 1
+    pub line: Option<usize>,
+    /// Column in the template, starting at 1
+    pub column: Option<usize>,
+    /// Length in characters of the part of the template that caused the error
+    pub length: Option<usize>,
+    /// Hint to fix the error
+    pub help: Option<String>,
+}
+
+impl Error {
+    /// Returns a value to display the error, using colors according to `color`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::{ColorChoice, SrTemplate};
+    ///
+    /// let ctx = SrTemplate::default();
+    /// let error = ctx.render("Hi {{ name }}").unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.display(ColorChoice::Never).to_string(),
+    ///     "Error: Variable not found: name\n --> 1:7\n 1 | Hi {{ name }}\n           ^^^^\n"
+    /// );
+    /// ```
+    pub fn display(&self, color: ColorChoice) -> ErrorDisplay<'_> {
+        ErrorDisplay {
+            error: self,
+            color: color.enabled(),
+        }
+    }
+
+    /// Returns the machine readable representation of the error
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic {
+            code: self.code(),
+            kind: self.kind(),
+            message: self.message(),
+            offset: None,
+            line: None,
+            column: None,
+            length: None,
+            help: None,
+        };
+
+        match self {
+            Error::BadSyntax(e) => {
+                diagnostic.offset = Some(e.at);
+                diagnostic.line = Some(e.line);
+                diagnostic.column = Some(e.column + 1);
+                diagnostic.length = Some(1);
+                diagnostic.help = Some(e.help.clone()).filter(|h| !h.is_empty());
+            }
+            Error::VariableNotFound(_, location)
+            | Error::FunctionNotImplemented(_, location)
+            | Error::Function(_, location) => {
+                diagnostic.offset = Some(location.at);
+                diagnostic.line = Some(location.line);
+                diagnostic.column = Some(location.column);
+                diagnostic.length = Some(location.len);
+                diagnostic.help = Some(location.help.clone()).filter(|h| !h.is_empty());
+            }
+            Error::Serialize(_) | Error::LoadVariables { .. } | Error::Write(_) => {}
+        }
+
+        diagnostic
+    }
+
+    /// Description of the error, without location nor colors
+    fn message(&self) -> String {
+        match self {
+            Error::BadSyntax(e) => e.kind.to_string(),
+            Error::VariableNotFound(name, _) => format!("Variable not found: {name}"),
+            Error::FunctionNotImplemented(name, _) => format!("Function not implemented: {name}"),
+            Error::Function(e, _) => format!("Error Processing Function: {e}"),
+            Error::Serialize(_) | Error::LoadVariables { .. } | Error::Write(_) => self.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ColorChoice, Error, ErrorKind, SrTemplate};
+
+    #[test]
+    fn colorless_syntax_error() {
+        let ctx = SrTemplate::default();
+        let error = ctx.render("Hi {{ trim(\"x }}").unwrap_err();
+
+        let text = error.display(ColorChoice::Never).to_string();
+        assert!(!text.contains('\x1b'));
+        assert!(text.starts_with("SyntaxError: Unterminated string literal\n"));
+
+        assert_eq!(
+            error.display(ColorChoice::Always).to_string(),
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn runtime_diagnostic() {
+        let ctx = SrTemplate::default();
+        ctx.add_variable("name", "World");
+        let error = ctx.render("Hi\n{{ Name }}").unwrap_err();
+
+        let diagnostic = error.diagnostic();
+        assert_eq!(diagnostic.code, "E0201");
+        assert_eq!(diagnostic.kind, ErrorKind::VariableNotFound);
+        assert_eq!(diagnostic.message, "Variable not found: Name");
+        assert_eq!(diagnostic.offset, Some(6));
+        assert_eq!((diagnostic.line, diagnostic.column), (Some(2), Some(4)));
+        assert_eq!(diagnostic.length, Some(4));
+        assert_eq!(
+            diagnostic.help.as_deref(),
+            Some("help: did you mean \"name\"?")
+        );
+    }
+
+    #[test]
+    fn diagnostic_without_location() {
+        let diagnostic = Error::Serialize("unsupported".to_owned()).diagnostic();
+
+        assert_eq!(diagnostic.code, "E0401");
+        assert_eq!(
+            diagnostic.message,
+            "Error serializing variables: unsupported"
+        );
+        assert_eq!(diagnostic.line, None);
+        assert_eq!(diagnostic.help, None);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_diagnostic() {
+        let ctx = SrTemplate::default();
+        let diagnostic = ctx.render("{{ nope(1) }}").unwrap_err().diagnostic();
+
+        assert_eq!(
+            serde_json::to_string(&diagnostic).unwrap(),
+            r#"{"code":"E0202","kind":"FunctionNotImplemented","message":"Function not implemented: nope","offset":3,"line":1,"column":4,"length":4,"help":null}"#
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+
+use crate::parser::SyntaxErrorKind;
+use crate::template::function::Error as FunctionError;
+
+use super::Error;
+
+/// Declares [`ErrorKind`] with the stable code of every variant
+macro_rules! error_kinds {
+    ($($(#[$doc:meta])* $kind:ident => $code:literal,)*) => {
+        /// Kind of an [`Error`], identified by a stable code
+        ///
+        /// The codes and names never change even if the messages of the errors are reworded,
+        /// so they can be used to group or match errors.
+        /// They are displayed together, like `E0101 UnterminatedString`.
+        ///
+        /// | Code | Kind |
+        /// |------|------|
+        /// | `E0101` | [`UnterminatedString`](ErrorKind::UnterminatedString) |
+        /// | `E0102` | [`UnterminatedArgument`](ErrorKind::UnterminatedArgument) |
+        /// | `E0103` | [`InvalidNumber`](ErrorKind::InvalidNumber) |
+        /// | `E0104` | [`FloatDotted`](ErrorKind::FloatDotted) |
+        /// | `E0105` | [`Expected`](ErrorKind::Expected) |
+        /// | `E0201` | [`VariableNotFound`](ErrorKind::VariableNotFound) |
+        /// | `E0202` | [`FunctionNotImplemented`](ErrorKind::FunctionNotImplemented) |
+        /// | `E0301` | [`InvalidArgument`](ErrorKind::InvalidArgument) |
+        /// | `E0302` | [`InvalidType`](ErrorKind::InvalidType) |
+        /// | `E0303` | [`ConvertArgsFailed`](ErrorKind::ConvertArgsFailed) |
+        /// | `E0304` | [`ArgumentsIncomplete`](ErrorKind::ArgumentsIncomplete) |
+        /// | `E0305` | [`RuntimeError`](ErrorKind::RuntimeError) |
+        /// | `E0306` | [`ParseArgument`](ErrorKind::ParseArgument) |
+        /// | `E0401` | [`Serialize`](ErrorKind::Serialize) |
+        /// | `E0402` | [`LoadVariables`](ErrorKind::LoadVariables) |
+        /// | `E0403` | [`Write`](ErrorKind::Write) |
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use srtemplate::{ErrorKind, SrTemplate};
+        ///
+        /// let ctx = SrTemplate::default();
+        /// let error = ctx.render("Hi {{ name }}").unwrap_err();
+        ///
+        /// assert_eq!(error.kind(), ErrorKind::VariableNotFound);
+        /// assert_eq!(error.code(), "E0201");
+        /// assert_eq!(error.kind().to_string(), "E0201 VariableNotFound");
+        /// ```
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+        #[non_exhaustive]
+        pub enum ErrorKind {
+            $($(#[$doc])* $kind,)*
+        }
+
+        impl ErrorKind {
+            /// Every kind of error, ordered by code
+            pub const ALL: &'static [ErrorKind] = &[$(ErrorKind::$kind,)*];
+
+            /// Stable code of the kind, like `E0101`
+            pub fn code(self) -> &'static str {
+                match self {
+                    $(ErrorKind::$kind => $code,)*
+                }
+            }
+
+            /// Stable name of the kind, like `UnterminatedString`
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(ErrorKind::$kind => stringify!($kind),)*
+                }
+            }
+        }
+    };
+}
+
+error_kinds! {
+    /// A string literal without closing quote
+    UnterminatedString => "E0101",
+    /// A function call without closing parenthesis
+    UnterminatedArgument => "E0102",
+    /// A number literal with invalid characters
+    InvalidNumber => "E0103",
+    /// A float literal with more than one `.`
+    FloatDotted => "E0104",
+    /// A token was expected, like the close delimiter
+    Expected => "E0105",
+    /// A variable that is not registered
+    VariableNotFound => "E0201",
+    /// A function that is not registered
+    FunctionNotImplemented => "E0202",
+    /// A function received invalid arguments
+    InvalidArgument => "E0301",
+    /// A function received an argument of an invalid type
+    InvalidType => "E0302",
+    /// The arguments of a function could not be converted to typed arguments
+    ConvertArgsFailed => "E0303",
+    /// A function received a wrong number of arguments
+    ArgumentsIncomplete => "E0304",
+    /// A function failed while running
+    RuntimeError => "E0305",
+    /// An argument of a function could not be parsed into the type of its parameter
+    ParseArgument => "E0306",
+    /// A value could not be converted into variables
+    Serialize => "E0401",
+    /// Variables could not be loaded from a file or string
+    LoadVariables => "E0402",
+    /// The output of a compiled template could not be written
+    Write => "E0403",
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.name())
+    }
+}
+
+impl Error {
+    /// Returns the kind of the error, for errors of functions it is the kind of the inner error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::BadSyntax(e) => ErrorKind::from(&e.kind),
+            Error::VariableNotFound(..) => ErrorKind::VariableNotFound,
+            Error::FunctionNotImplemented(..) => ErrorKind::FunctionNotImplemented,
+            Error::Function(e, _) => e.kind(),
+            Error::Serialize(_) => ErrorKind::Serialize,
+            Error::LoadVariables { .. } => ErrorKind::LoadVariables,
+            Error::Write(_) => ErrorKind::Write,
+        }
+    }
+
+    /// Returns the stable code of the error, like `E0201`
+    pub fn code(&self) -> &'static str {
+        self.kind().code()
+    }
+}
+
+impl From<&SyntaxErrorKind> for ErrorKind {
+    /// Kind of the syntax error
+    fn from(kind: &SyntaxErrorKind) -> Self {
+        match kind {
+            SyntaxErrorKind::UnterminatedString => ErrorKind::UnterminatedString,
+            SyntaxErrorKind::UnterminatedArgument => ErrorKind::UnterminatedArgument,
+            SyntaxErrorKind::InvalidNumber => ErrorKind::InvalidNumber,
+            SyntaxErrorKind::FloatDotted => ErrorKind::FloatDotted,
+            SyntaxErrorKind::Expected(..) => ErrorKind::Expected,
+        }
+    }
+}
+
+impl FunctionError {
+    /// Returns the kind of the function error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            FunctionError::InvalidArgument(_) => ErrorKind::InvalidArgument,
+            FunctionError::InvalidType(_) => ErrorKind::InvalidType,
+            #[cfg(feature = "typed_args")]
+            FunctionError::ConvertArgsFailed(_) => ErrorKind::ConvertArgsFailed,
+            FunctionError::ArgumentsIncomplete(..) => ErrorKind::ArgumentsIncomplete,
+            FunctionError::RuntimeError(_) => ErrorKind::RuntimeError,
+            FunctionError::ParseArgument(_) => ErrorKind::ParseArgument,
+        }
+    }
+
+    /// Returns the stable code of the function error, like `E0304`
+    pub fn code(&self) -> &'static str {
+        self.kind().code()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::error::Location;
+    use crate::parser::SyntaxErrorKind;
+    use crate::prelude::FunctionError;
+    use crate::{Error, ErrorKind, SrTemplate};
+
+    #[test]
+    fn stable_codes() {
+        let codes = ErrorKind::ALL
+            .iter()
+            .map(|kind| kind.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            codes,
+            [
+                "E0101 UnterminatedString",
+                "E0102 UnterminatedArgument",
+                "E0103 InvalidNumber",
+                "E0104 FloatDotted",
+                "E0105 Expected",
+                "E0201 VariableNotFound",
+                "E0202 FunctionNotImplemented",
+                "E0301 InvalidArgument",
+                "E0302 InvalidType",
+                "E0303 ConvertArgsFailed",
+                "E0304 ArgumentsIncomplete",
+                "E0305 RuntimeError",
+                "E0306 ParseArgument",
+                "E0401 Serialize",
+                "E0402 LoadVariables",
+                "E0403 Write",
+            ]
+        );
+        let unique = ErrorKind::ALL
+            .iter()
+            .map(|kind| kind.code())
+            .collect::<HashSet<_>>();
+        assert_eq!(unique.len(), ErrorKind::ALL.len());
+    }
+
+    #[test]
+    fn error_kinds() {
+        let ctx = SrTemplate::default();
+        ctx.add_function("fail", |_| {
+            Err(FunctionError::RuntimeError("boom".to_owned()))
+        });
+
+        let kind = |template: &str| ctx.render(template).unwrap_err().kind();
+
+        assert_eq!(kind("{{ trim(\"x }}"), ErrorKind::UnterminatedString);
+        assert_eq!(kind("{{ f(1x) }}"), ErrorKind::InvalidNumber);
+        assert_eq!(kind("{{ name"), ErrorKind::Expected);
+        assert_eq!(kind("{{ name }}"), ErrorKind::VariableNotFound);
+        assert_eq!(kind("{{ nope() }}"), ErrorKind::FunctionNotImplemented);
+        assert_eq!(kind("{{ fail() }}"), ErrorKind::RuntimeError);
+        assert_eq!(
+            Error::Serialize(String::new()).code(),
+            ErrorKind::Serialize.code()
+        );
+    }
+
+    #[test]
+    fn nested_kinds() {
+        let error = Error::Function(
+            FunctionError::ArgumentsIncomplete(1, 2),
+            Location::default(),
+        );
+
+        assert_eq!(error.code(), "E0304");
+        assert_eq!(
+            ErrorKind::from(&SyntaxErrorKind::FloatDotted).code(),
+            "E0104"
+        );
+    }
+}
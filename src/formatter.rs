@@ -0,0 +1,201 @@
+use crate::ast::{parse, TemplateNode};
+use crate::error::Error;
+
+/// Options to format a template with [`format`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions<'o> {
+    /// Start delimiter of the expressions, `{{` by default
+    pub start: &'o str,
+    /// Close delimiter of the expressions, `}}` by default
+    pub close: &'o str,
+    /// Whether to put a space between the delimiters and the expression,
+    /// like `{{ var }}` instead of `{{var}}`, `true` by default
+    pub spaces: bool,
+}
+
+impl Default for FormatOptions<'_> {
+    fn default() -> Self {
+        Self {
+            start: "{{",
+            close: "}}",
+            spaces: true,
+        }
+    }
+}
+
+/// Formats a template, normalizing the expressions inside the delimiters
+///
+/// The spacing inside the delimiters depends on [`FormatOptions::spaces`], function
+/// arguments are separated by `, ` and strings are always quoted with `"`.
+/// The text outside of the delimiters is preserved byte for byte, so parsing the
+/// formatted template yields the same nodes, only their spans change.
+///
+/// # Arguments
+///
+/// * `template`: The template to format.
+/// * `options`: The delimiters of the template and the style of the output.
+///
+/// # Returns
+///
+/// The formatted template.
+///
+/// # Errors
+///
+/// Returns [`Error::BadSyntax`] if the syntax of the template is invalid.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate::{format, FormatOptions};
+///
+/// let template = "Hi {{name}} and {{  toLower( trim(other ,\"x\"))}}!";
+///
+/// assert_eq!(
+///     format(template, &FormatOptions::default()).unwrap(),
+///     "Hi {{ name }} and {{ toLower(trim(other, \"x\")) }}!"
+/// );
+/// ```
+pub fn format(template: &str, options: &FormatOptions) -> Result<String, Error> {
+    let nodes = parse(template, options.start, options.close)?;
+    let padding = if options.spaces { " " } else { "" };
+    let mut res = String::with_capacity(template.len());
+
+    for node in &nodes {
+        if let TemplateNode::RawText(text, _) = node {
+            res.push_str(text);
+            continue;
+        }
+
+        res.push_str(options.start);
+        res.push_str(padding);
+        expression(&mut res, node);
+        res.push_str(padding);
+        res.push_str(options.close);
+    }
+
+    Ok(res)
+}
+
+fn expression(res: &mut String, node: &TemplateNode) {
+    match node {
+        TemplateNode::Function(name, args, _) => {
+            res.push_str(name);
+            res.push('(');
+            for (i, arg) in args.iter().enumerate() {
+                if i > 0 {
+                    res.push_str(", ");
+                }
+                expression(res, arg);
+            }
+            res.push(')');
+        }
+        TemplateNode::String(value, _) => {
+            res.push('"');
+            res.push_str(value);
+            res.push('"');
+        }
+        TemplateNode::Variable(text, _)
+        | TemplateNode::Number(text, _)
+        | TemplateNode::Float(text, _)
+        | TemplateNode::RawText(text, _) => res.push_str(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Describes the nodes without their spans
+    fn shape(nodes: &[TemplateNode]) -> String {
+        nodes
+            .iter()
+            .map(|node| {
+                let kind = match node {
+                    TemplateNode::Function(name, args, _) => {
+                        return format!("Function({name:?}, [{}])", shape(args))
+                    }
+                    TemplateNode::Variable(..) => "Variable",
+                    TemplateNode::String(..) => "String",
+                    TemplateNode::Number(..) => "Number",
+                    TemplateNode::Float(..) => "Float",
+                    TemplateNode::RawText(..) => "RawText",
+                };
+                format!("{kind}({:?})", node.text())
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    #[test]
+    fn normalize_spacing() {
+        let options = FormatOptions::default();
+
+        assert_eq!(format("{{var}}", &options).unwrap(), "{{ var }}");
+        assert_eq!(format("{{  var}}", &options).unwrap(), "{{ var }}");
+        assert_eq!(
+            format("{{f(a,1,  2.5,\"x, y\")}}", &options).unwrap(),
+            "{{ f(a, 1, 2.5, \"x, y\") }}"
+        );
+        assert_eq!(format("{{ f( ) }}", &options).unwrap(), "{{ f() }}");
+    }
+
+    #[test]
+    fn compact_with_custom_delimiters() {
+        let options = FormatOptions {
+            start: "<%",
+            close: "%>",
+            spaces: false,
+        };
+
+        assert_eq!(
+            format("a <% x %> {{ y }} <%g( x )%>", &options).unwrap(),
+            "a <%x%> {{ y }} <%g(x)%>"
+        );
+    }
+
+    #[test]
+    fn preserve_raw_text() {
+        let template = "  line one\n\t{{x}}  \r\n é {{ y }}\n";
+        let formatted = format(template, &FormatOptions::default()).unwrap();
+
+        assert_eq!(formatted, "  line one\n\t{{ x }}  \r\n é {{ y }}\n");
+    }
+
+    #[test]
+    fn round_trip() {
+        let options = FormatOptions::default();
+        let templates = [
+            "Hi {{name}}!",
+            "{{ toLower(trim( user.name ,\"\\\"q\\\"\"), 10,0.5) }} end",
+            "{{a}}{{b}}\n{{ c(d(e(f))) }}",
+            "{{f(a,1,  2.5,\"x, y\")}}",
+        ];
+
+        for template in templates {
+            let formatted = format(template, &options).unwrap();
+
+            assert_eq!(
+                shape(&parse(template, "{{", "}}").unwrap()),
+                shape(&parse(&formatted, "{{", "}}").unwrap())
+            );
+            assert_eq!(format(&formatted, &options).unwrap(), formatted);
+        }
+    }
+
+    #[test]
+    fn shape_compares_text() {
+        let shape = |template| shape(&parse(template, "{{", "}}").unwrap());
+
+        assert_eq!(
+            shape("{{ f(\"x, y\") }}"),
+            "Function(\"f\", [String(\"x, y\")])"
+        );
+        assert_ne!(shape("{{ f(\"x, y\") }}"), shape("{{ f(\"x, z\") }}"));
+        assert_eq!(shape("{{ a }}!"), shape("{{a}}!"));
+    }
+
+    #[test]
+    fn invalid_template() {
+        assert!(format("{{ f(", &FormatOptions::default()).is_err());
+    }
+}
@@ -6,7 +6,13 @@
 //! - `math`: Mathematical functions.
 //! - `typed_args`: Enables typed arguments, if specified.
 //! - `debug`: Enable log for library
-//! - `macros`: Enable a easy way to create custom functions
+//! - `macros`: Enable a easy way to create custom functions and to parse templates at compile time
+//! - `serde`: Enable adding any `serde::Serialize` value as variables and serializing error diagnostics
+//! - `json`: Enable loading variables from JSON files
+//! - `toml`: Enable loading variables from TOML files
+//! - `yaml`: Enable loading variables from YAML files
+//! - `dotenv`: Enable loading variables from `.env` files
+//! - `cli`: Build the `srtemplate` command line binary
 //! - `default`: Enable all features excepts `debug` feature
 //!
 //! To enable specific features, you can include them in your dependencies, like:
@@ -33,16 +39,28 @@
 //!
 //! To see all function implemented for template syntax see [wiki](https://github.com/SergioRibera/srtemplate/wiki/Template-Syntaxis#builtin-functions)
 
+/// The `ast` module exposes the nodes of a parsed template and visitors to traverse them.
+pub mod ast;
+
 /// The `builtin` module provides a set of built-in functions for `SrTemplate`.
 pub mod builtin;
 
+/// The `compiled` module provides the variables and runtime support of templates compiled to Rust code.
+pub mod compiled;
+
 /// The `error` module defines custom error types for `SrTemplate`.
 mod error;
 
+/// The `formatter` module normalizes the expressions of templates.
+mod formatter;
+
 /// The `helper` module contains utility functions and traits, and it's available when the `typed_args` feature is enabled.
 #[cfg(feature = "typed_args")]
 pub mod helper;
 
+/// The `lint` module checks templates against configurable rules.
+pub mod lint;
+
 /// The `parser` module is responsible for parsing template strings into nodes.
 mod parser;
 
@@ -53,19 +71,29 @@ mod render;
 mod template;
 
 /// Re-exports the `SrTemplateError` type for convenient use.
-pub use error::Error;
+pub use error::{ColorChoice, Diagnostic, Error, ErrorDisplay, ErrorKind, Location};
+
+/// Re-exports the [`Variables`] trait used by compiled templates.
+pub use compiled::Variables;
+
+/// Re-exports the [`format`] function to normalize templates.
+pub use formatter::{format, FormatOptions};
 
 /// Re-exports the [`template::function`], [`template::SrTemplate`], [`template::TemplateFunction`] type for convenient use.
-pub use template::{function, Function, SrTemplate};
+pub use template::{function, Function, Reference, ReferenceKind, SrTemplate};
 
 #[cfg(feature = "macros")]
-pub use helper_macros::{function, Variable};
+pub use helper_macros::{
+    compile_template, function, include_template, template, Template, Variable,
+};
 
 /// The `prelude` module re-exports common items for easier use of `SrTemplate`.
 pub mod prelude {
     pub use super::builtin::*;
-    pub use super::error::Error;
-    pub use super::template::function::{Error as FunctionError, FuncResult};
+    pub use super::error::{ColorChoice, Diagnostic, Error, ErrorKind, Location};
+    pub use super::template::function::{
+        Error as FunctionError, FuncResult, FunctionParam, FunctionSignature, ParseArgumentError,
+    };
     pub use super::template::validations;
     pub use super::{Function, SrTemplate};
 
@@ -76,10 +104,49 @@ pub mod prelude {
 
 pub trait Variable<'a> {
     fn variables(&self) -> impl Iterator<Item = (std::borrow::Cow<'a, str>, String)>;
+
+    /// Returns the variables without the prefix of the value, like `city` instead of
+    /// `Address.city`, an empty name is the value itself.
+    ///
+    /// It is used to nest and flatten values in `#[derive(Variable)]`, by default the
+    /// prefix is the part of the names before the first `.`.
+    fn fields(&self) -> impl Iterator<Item = (std::borrow::Cow<'a, str>, String)> {
+        self.variables().map(|(name, value)| {
+            let field = match &name {
+                std::borrow::Cow::Borrowed(name) => {
+                    std::borrow::Cow::Borrowed(name.split_once('.').map_or("", |(_, field)| field))
+                }
+                std::borrow::Cow::Owned(name) => std::borrow::Cow::Owned(
+                    name.split_once('.')
+                        .map_or("", |(_, field)| field)
+                        .to_owned(),
+                ),
+            };
+            (field, value)
+        })
+    }
 }
 
 impl<'a, T: Variable<'a>> Variable<'a> for &T {
     fn variables(&self) -> impl Iterator<Item = (std::borrow::Cow<'a, str>, String)> {
         T::variables(self)
     }
+
+    fn fields(&self) -> impl Iterator<Item = (std::borrow::Cow<'a, str>, String)> {
+        T::fields(self)
+    }
+}
+
+/// Joins a prefix and the name of a field, an empty name is the prefix itself
+#[doc(hidden)]
+pub fn join_name<'a>(
+    prefix: impl Into<std::borrow::Cow<'a, str>>,
+    name: std::borrow::Cow<'a, str>,
+) -> std::borrow::Cow<'a, str> {
+    let prefix = prefix.into();
+    match (prefix.is_empty(), name.is_empty()) {
+        (true, _) => name,
+        (false, true) => prefix,
+        (false, false) => format!("{prefix}.{name}").into(),
+    }
 }
@@ -0,0 +1,421 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::ast::{parse, walk_function, Span, TemplateNode, Visitor};
+use crate::error::{Error, Location, Snippet};
+
+/// How serious a [`Lint`] is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Severity {
+    /// The template works, but it should be changed
+    Warning,
+    /// The template must not be used
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// The checks done by the [`Linter`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum LintRule {
+    /// Calls to functions denied with [`Linter::deny_function`]
+    DeniedFunction,
+    /// Calls to functions deprecated with [`Linter::deprecate_function`]
+    DeprecatedFunction,
+    /// Variables not declared with [`Linter::allow_variables`]
+    UnknownVariable,
+    /// Function calls nested deeper than [`Linter::max_depth`]
+    DeepNesting,
+    /// Typed math functions receiving values of another type, like `add_u8(1.5)`
+    /// or `add_u8(mul_f32(1, 2))`
+    MixedMath,
+}
+
+impl LintRule {
+    /// Severity of the rule unless it is changed with [`Linter::severity`]
+    pub fn default_severity(self) -> Severity {
+        match self {
+            LintRule::DeniedFunction | LintRule::UnknownVariable => Severity::Error,
+            LintRule::DeprecatedFunction | LintRule::DeepNesting | LintRule::MixedMath => {
+                Severity::Warning
+            }
+        }
+    }
+}
+
+/// A problem found by the [`Linter`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lint {
+    /// The rule that found the problem
+    pub rule: LintRule,
+    /// How serious the problem is
+    pub severity: Severity,
+    /// Description of the problem
+    pub message: String,
+    /// Byte range of the node in the template
+    pub span: Span,
+    /// Line, column and context of the node
+    pub location: Location,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = self.severity.to_string();
+
+        Snippet {
+            title: &title,
+            ..self.location.snippet(&self.message)
+        }
+        .fmt(f)
+    }
+}
+
+/// Checks templates against a configurable set of rules
+///
+/// Every rule of [`LintRule`] is enabled with its default severity, but
+/// [`LintRule::DeniedFunction`], [`LintRule::DeprecatedFunction`] and
+/// [`LintRule::UnknownVariable`] only report something once they are configured.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate::lint::{LintRule, Linter, Severity};
+///
+/// let linter = Linter::default()
+///     .deny_function("env")
+///     .allow_variables(["user.name"]);
+///
+/// let lints = linter.lint("Hi {{ user.name }} from {{ env(\"HOST\") }} {{ token }}").unwrap();
+///
+/// assert_eq!(lints.len(), 2);
+/// assert_eq!(lints[0].rule, LintRule::DeniedFunction);
+/// assert_eq!(lints[1].rule, LintRule::UnknownVariable);
+/// assert_eq!(lints[1].severity, Severity::Error);
+/// assert_eq!((lints[1].location.line, lints[1].location.column), (1, 46));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Linter<'a> {
+    start: Cow<'a, str>,
+    close: Cow<'a, str>,
+    denied: HashSet<String>,
+    deprecated: HashMap<String, Option<String>>,
+    allowed_variables: Option<HashSet<String>>,
+    max_depth: usize,
+    severities: HashMap<LintRule, Option<Severity>>,
+}
+
+impl Default for Linter<'_> {
+    /// Generates a linter for templates delimited by `{{` and `}}`, allowing 4 nested calls
+    fn default() -> Self {
+        Self {
+            start: "{{".into(),
+            close: "}}".into(),
+            denied: HashSet::new(),
+            deprecated: HashMap::new(),
+            allowed_variables: None,
+            max_depth: 4,
+            severities: HashMap::new(),
+        }
+    }
+}
+
+impl<'a> Linter<'a> {
+    /// Generates a linter for templates that use the given delimiters
+    pub fn with_delimiter(start: impl Into<Cow<'a, str>>, close: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            start: start.into(),
+            close: close.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Reports every call to the function, like `env` in templates written by users
+    pub fn deny_function(mut self, name: impl Into<String>) -> Self {
+        self.denied.insert(name.into());
+        self
+    }
+
+    /// Reports every call to the function, suggesting the `replacement` if any
+    pub fn deprecate_function(
+        mut self,
+        name: impl Into<String>,
+        replacement: Option<&str>,
+    ) -> Self {
+        self.deprecated
+            .insert(name.into(), replacement.map(str::to_owned));
+        self
+    }
+
+    /// Reports every variable that is not one of `names`, can be called several times
+    pub fn allow_variables<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_variables
+            .get_or_insert_with(HashSet::new)
+            .extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Reports function calls nested deeper than `depth`, e.g. `f(g(x))` has a depth of 2
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Changes the severity of the rule
+    pub fn severity(mut self, rule: LintRule, severity: Severity) -> Self {
+        self.severities.insert(rule, Some(severity));
+        self
+    }
+
+    /// Disables the rule
+    pub fn disable(mut self, rule: LintRule) -> Self {
+        self.severities.insert(rule, None);
+        self
+    }
+
+    /// Checks the template, returning the problems found in the order they appear
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BadSyntax`] if the syntax of the template is invalid.
+    pub fn lint(&self, template: &str) -> Result<Vec<Lint>, Error> {
+        let nodes = parse(template, &self.start, &self.close)?;
+        let mut checker = Checker {
+            linter: self,
+            input: template,
+            depth: 0,
+            lints: Vec::new(),
+        };
+
+        checker.visit_nodes(&nodes);
+        Ok(checker.lints)
+    }
+}
+
+struct Checker<'l, 't> {
+    linter: &'l Linter<'l>,
+    input: &'t str,
+    depth: usize,
+    lints: Vec<Lint>,
+}
+
+impl Checker<'_, '_> {
+    fn report(&mut self, rule: LintRule, span: &Span, message: String) {
+        let severity = match self.linter.severities.get(&rule) {
+            Some(None) => return,
+            Some(Some(severity)) => *severity,
+            None => rule.default_severity(),
+        };
+
+        self.lints.push(Lint {
+            rule,
+            severity,
+            message,
+            span: span.clone(),
+            location: Location::new(self.input, span.clone()),
+        });
+    }
+
+    fn check_math(&mut self, name: &str, args: &[TemplateNode]) {
+        let Some(expected) = math_type(name) else {
+            return;
+        };
+
+        for arg in args {
+            match arg {
+                TemplateNode::Float(value, span) if !expected.starts_with('f') => self.report(
+                    LintRule::MixedMath,
+                    span,
+                    format!("`{name}` works with {expected}, but receives the decimal {value}"),
+                ),
+                TemplateNode::Function(inner, _, span) => match math_type(inner) {
+                    Some(found) if found != expected => self.report(
+                        LintRule::MixedMath,
+                        span,
+                        format!("`{name}` works with {expected}, but `{inner}` returns {found}"),
+                    ),
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'t> Visitor<'t> for Checker<'_, 't> {
+    fn visit_variable(&mut self, name: &'t str, span: &Span) {
+        if let Some(allowed) = &self.linter.allowed_variables {
+            if !allowed.contains(name) {
+                self.report(
+                    LintRule::UnknownVariable,
+                    span,
+                    format!("Variable `{name}` is not allowed"),
+                );
+            }
+        }
+    }
+
+    fn visit_function(&mut self, name: &'t str, args: &[TemplateNode<'t>], span: &Span) {
+        let name_span = span.start..span.start + name.len();
+
+        if self.linter.denied.contains(name) {
+            self.report(
+                LintRule::DeniedFunction,
+                &name_span,
+                format!("Function `{name}` is not allowed"),
+            );
+        }
+        if let Some(replacement) = self.linter.deprecated.get(name) {
+            let message = match replacement {
+                Some(replacement) => {
+                    format!("Function `{name}` is deprecated, use `{replacement}` instead")
+                }
+                None => format!("Function `{name}` is deprecated"),
+            };
+            self.report(LintRule::DeprecatedFunction, &name_span, message);
+        }
+
+        self.depth += 1;
+        if self.depth == self.linter.max_depth + 1 {
+            let max_depth = self.linter.max_depth;
+            self.report(
+                LintRule::DeepNesting,
+                span,
+                format!("Function calls are nested deeper than {max_depth} levels"),
+            );
+        }
+
+        self.check_math(name, args);
+        walk_function(self, args);
+        self.depth -= 1;
+    }
+}
+
+/// Type of the values of a builtin math function, like `u8` for `add_u8` or `i32` for `add`
+fn math_type(name: &str) -> Option<&str> {
+    const OPERATIONS: [&str; 4] = ["add", "sub", "mul", "div"];
+    const TYPES: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+    ];
+
+    if OPERATIONS.contains(&name) {
+        return Some("i32");
+    }
+
+    let (operation, ty) = name.split_once('_')?;
+    (OPERATIONS.contains(&operation) && TYPES.contains(&ty)).then_some(ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(lints: &[Lint]) -> Vec<(LintRule, Severity, Span)> {
+        lints
+            .iter()
+            .map(|l| (l.rule, l.severity, l.span.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn denied_and_deprecated_functions() {
+        let linter = Linter::default()
+            .deny_function("env")
+            .deprecate_function("toLowerCase", Some("toLower"))
+            .deprecate_function("old", None);
+        let lints = linter
+            .lint("{{ toLowerCase(env(\"USER\")) }} {{ old() }}")
+            .unwrap();
+
+        assert_eq!(
+            rules(&lints),
+            [
+                (LintRule::DeprecatedFunction, Severity::Warning, 3..14),
+                (LintRule::DeniedFunction, Severity::Error, 15..18),
+                (LintRule::DeprecatedFunction, Severity::Warning, 34..37),
+            ]
+        );
+        assert_eq!(
+            lints[0].message,
+            "Function `toLowerCase` is deprecated, use `toLower` instead"
+        );
+    }
+
+    #[test]
+    fn allowed_variables() {
+        let linter = Linter::default().allow_variables(["name"]);
+        let lints = linter.lint("{{ name }} {{ trim(nme) }}").unwrap();
+
+        assert_eq!(
+            rules(&lints),
+            [(LintRule::UnknownVariable, Severity::Error, 19..22)]
+        );
+        assert!(Linter::default().lint("{{ nme }}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn deep_nesting() {
+        let linter = Linter::default().max_depth(2);
+
+        assert!(linter.lint("{{ a(b(x)) }}").unwrap().is_empty());
+        assert_eq!(
+            rules(&linter.lint("{{ a(b(c(d(x)))) }}").unwrap()),
+            [(LintRule::DeepNesting, Severity::Warning, 7..14)]
+        );
+    }
+
+    #[test]
+    fn mixed_math() {
+        let lints = Linter::default()
+            .lint("{{ add_u8(1, 2.5) }} {{ add_f32(1.5, 2) }} {{ mul(add_u8(1, 2), 3) }}")
+            .unwrap();
+
+        assert_eq!(
+            rules(&lints),
+            [
+                (LintRule::MixedMath, Severity::Warning, 13..16),
+                (LintRule::MixedMath, Severity::Warning, 50..62),
+            ]
+        );
+        assert_eq!(
+            lints[1].message,
+            "`mul` works with i32, but `add_u8` returns u8"
+        );
+    }
+
+    #[test]
+    fn configure_severities() {
+        let linter = Linter::with_delimiter("<%", "%>")
+            .deny_function("env")
+            .severity(LintRule::DeniedFunction, Severity::Warning)
+            .disable(LintRule::MixedMath);
+        let lints = linter.lint("<% env(add_u8(1.5)) %>").unwrap();
+
+        assert_eq!(
+            rules(&lints),
+            [(LintRule::DeniedFunction, Severity::Warning, 3..6)]
+        );
+        assert!(lints[0]
+            .to_string()
+            .contains("warning:\x1b[0m Function `env` is not allowed"));
+    }
+
+    #[test]
+    fn invalid_template() {
+        assert!(Linter::default().lint("{{ f(").is_err());
+    }
+}
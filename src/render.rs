@@ -1,13 +1,71 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 use dashmap::DashMap;
 
-use crate::error::Error;
-use crate::parser::TemplateNode;
+use crate::ast::TemplateNode;
+use crate::error::{Error, Location};
 use crate::template::Function;
 #[cfg(feature = "debug")]
 use log::debug;
 
+/// Variables that can be referenced while rendering
+pub trait Scope {
+    /// Calls `f` with the value of the variable `name`, if it exists
+    fn with<R>(&self, name: &str, f: impl FnOnce(&str) -> R) -> Option<R>;
+
+    /// Names of every variable, used to suggest the ones similar to a missing variable
+    fn names(&self) -> Vec<String>;
+}
+
+impl Scope for DashMap<Cow<'_, str>, String> {
+    fn with<R>(&self, name: &str, f: impl FnOnce(&str) -> R) -> Option<R> {
+        self.get(name).map(|value| f(&value))
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.iter().map(|v| v.key().to_string()).collect()
+    }
+}
+
+impl Scope for HashMap<Cow<'_, str>, String> {
+    fn with<R>(&self, name: &str, f: impl FnOnce(&str) -> R) -> Option<R> {
+        self.get(name).map(|value| f(value))
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.keys().map(ToString::to_string).collect()
+    }
+}
+
+/// Variables added only for a render, layered over the variables of the context
+/// so they take precedence without copying the context
+pub struct Overlay<'s, B: ?Sized, E: ?Sized> {
+    pub base: &'s B,
+    pub extra: &'s E,
+}
+
+impl<B: Scope + ?Sized, E: Scope + ?Sized> Scope for Overlay<'_, B, E> {
+    fn with<R>(&self, name: &str, f: impl FnOnce(&str) -> R) -> Option<R> {
+        if self.extra.with(name, |_| ()).is_some() {
+            self.extra.with(name, f)
+        } else {
+            self.base.with(name, f)
+        }
+    }
+
+    fn names(&self) -> Vec<String> {
+        let mut names = self.extra.names();
+        names.extend(
+            self.base
+                .names()
+                .into_iter()
+                .filter(|name| self.extra.with(name, |_| ()).is_none()),
+        );
+        names
+    }
+}
+
 /// Renders a vector of `TemplateNode`s, replacing variables and processing functions.
 ///
 /// This function processes a list of `TemplateNode`s and returns a `Result` containing the rendered template as a `String` or a [`SrTemplateError`] in case of an error.
@@ -15,7 +73,8 @@ use log::debug;
 /// # Arguments
 ///
 /// * `nodes`: A vector of `TemplateNode`s to be processed.
-/// * `vars`: A reference to a `DashMap` containing variable names as keys and `Cow<'_, str>` as values.
+/// * `input`: The template the nodes were parsed from, used to locate the errors.
+/// * `vars`: The variables of the render, like a `DashMap` with variable names as keys.
 /// * `funcs`: A reference to a `DashMap` containing function names as keys and `TemplateFunction` closures as values.
 ///
 /// # Returns
@@ -23,37 +82,36 @@ use log::debug;
 /// A `Result` where `Ok` contains the rendered template as a `String`, and `Err` holds a [`SrTemplateError`] if an error occurs.
 pub fn nodes(
     res: &mut String,
-    tnode: TemplateNode,
-    vars: &DashMap<Cow<'_, str>, String>,
+    tnode: &TemplateNode,
+    input: &str,
+    vars: &(impl Scope + ?Sized),
     funcs: &DashMap<Cow<'_, str>, Box<Function>>,
 ) -> Result<(), Error> {
     match tnode {
-        TemplateNode::RawText(text)
-        | TemplateNode::String(text)
-        | TemplateNode::Float(text)
-        | TemplateNode::Number(text) => res.push_str(text),
-        TemplateNode::Variable(variable) => {
-            let variable = vars
-                .get(variable)
-                .ok_or(Error::VariableNotFound(variable.to_owned()))?;
-
-            res.push_str(&variable);
+        TemplateNode::RawText(text, _)
+        | TemplateNode::String(text, _)
+        | TemplateNode::Float(text, _)
+        | TemplateNode::Number(text, _) => res.push_str(text),
+        TemplateNode::Variable(variable, span) => {
+            vars.with(variable, |value| res.push_str(value))
+                .ok_or_else(|| {
+                    variable_not_found(variable, Location::new(input, span.clone()), vars)
+                })?;
         }
-        TemplateNode::Function(function, arguments) => {
+        TemplateNode::Function(function, arguments, span) => {
             let evaluated_arguments: Result<Vec<String>, Error> = arguments
-                .into_iter()
-                .map(|arg| node(arg, vars, funcs))
+                .iter()
+                .map(|arg| node(arg, input, vars, funcs))
                 .collect();
 
             let evaluated_arguments = evaluated_arguments?;
             #[cfg(feature = "debug")]
             debug!("Evaluated Args: {evaluated_arguments:?}");
 
-            let result_of_function = funcs
-                .get(function)
-                .ok_or(Error::FunctionNotImplemented(function.to_owned()))?(
-                &evaluated_arguments
-            )?;
+            let result_of_function = funcs.get(*function).ok_or_else(|| {
+                function_not_implemented(function, Location::of_name(input, function, span), funcs)
+            })?(&evaluated_arguments)
+            .map_err(|e| Error::Function(e, Location::new(input, span.clone())))?;
 
             #[cfg(feature = "debug")]
             debug!("Result of function: {result_of_function:?}");
@@ -66,37 +124,33 @@ pub fn nodes(
 }
 
 pub fn node(
-    tnode: TemplateNode,
-    vars: &DashMap<Cow<'_, str>, String>,
+    tnode: &TemplateNode,
+    input: &str,
+    vars: &(impl Scope + ?Sized),
     funcs: &DashMap<Cow<'_, str>, Box<Function>>,
 ) -> Result<String, Error> {
     match tnode {
-        TemplateNode::RawText(text)
-        | TemplateNode::String(text)
-        | TemplateNode::Float(text)
-        | TemplateNode::Number(text) => Ok(text.to_owned()),
-        TemplateNode::Variable(variable) => {
-            let variable = vars
-                .get(variable)
-                .ok_or(Error::VariableNotFound(variable.to_owned()))?;
-
-            Ok(variable.to_owned())
-        }
-        TemplateNode::Function(function, arguments) => {
+        TemplateNode::RawText(text, _)
+        | TemplateNode::String(text, _)
+        | TemplateNode::Float(text, _)
+        | TemplateNode::Number(text, _) => Ok(text.to_string()),
+        TemplateNode::Variable(variable, span) => vars
+            .with(variable, str::to_owned)
+            .ok_or_else(|| variable_not_found(variable, Location::new(input, span.clone()), vars)),
+        TemplateNode::Function(function, arguments, span) => {
             let evaluated_arguments: Result<Vec<String>, Error> = arguments
-                .into_iter()
-                .map(|arg| node(arg, vars, funcs))
+                .iter()
+                .map(|arg| node(arg, input, vars, funcs))
                 .collect();
 
             let evaluated_arguments = evaluated_arguments?;
             #[cfg(feature = "debug")]
             debug!("Evaluated Args: {evaluated_arguments:?}");
 
-            let result_of_function = funcs
-                .get(function)
-                .ok_or(Error::FunctionNotImplemented(function.to_owned()))?(
-                &evaluated_arguments
-            )?;
+            let result_of_function = funcs.get(*function).ok_or_else(|| {
+                function_not_implemented(function, Location::of_name(input, function, span), funcs)
+            })?(&evaluated_arguments)
+            .map_err(|e| Error::Function(e, Location::new(input, span.clone())))?;
 
             #[cfg(feature = "debug")]
             debug!("Result of function: {result_of_function:?}");
@@ -106,9 +160,91 @@ pub fn node(
     }
 }
 
+/// Renders a `TemplateNode` like [`nodes`], but instead of stopping at the first error
+/// every error is pushed into `errors` and the failed node renders nothing.
+pub fn nodes_recovering(
+    res: &mut String,
+    tnode: &TemplateNode,
+    input: &str,
+    vars: &(impl Scope + ?Sized),
+    funcs: &DashMap<Cow<'_, str>, Box<Function>>,
+    errors: &mut Vec<Error>,
+) {
+    if let Some(rendered) = node_recovering(tnode, input, vars, funcs, errors) {
+        res.push_str(&rendered);
+    }
+}
+
+/// Evaluates a `TemplateNode` like [`node`], collecting the errors of every argument.
+///
+/// A function is only called if it exists and all of its arguments were evaluated.
+pub fn node_recovering(
+    tnode: &TemplateNode,
+    input: &str,
+    vars: &(impl Scope + ?Sized),
+    funcs: &DashMap<Cow<'_, str>, Box<Function>>,
+    errors: &mut Vec<Error>,
+) -> Option<String> {
+    match tnode {
+        TemplateNode::RawText(text, _)
+        | TemplateNode::String(text, _)
+        | TemplateNode::Float(text, _)
+        | TemplateNode::Number(text, _) => Some(text.to_string()),
+        TemplateNode::Variable(variable, span) => {
+            let value = vars.with(variable, str::to_owned);
+            if value.is_none() {
+                errors.push(variable_not_found(
+                    variable,
+                    Location::new(input, span.clone()),
+                    vars,
+                ));
+            }
+            value
+        }
+        TemplateNode::Function(function, arguments, span) => {
+            let func = funcs.get(*function).map(|f| **f);
+            if func.is_none() {
+                errors.push(function_not_implemented(
+                    function,
+                    Location::of_name(input, function, span),
+                    funcs,
+                ));
+            }
+
+            let evaluated_arguments = arguments
+                .iter()
+                .map(|arg| node_recovering(arg, input, vars, funcs, errors))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .collect::<Option<Vec<String>>>()?;
+
+            func?(&evaluated_arguments)
+                .map_err(|e| errors.push(Error::Function(e, Location::new(input, span.clone()))))
+                .ok()
+        }
+    }
+}
+
+/// Builds [`Error::VariableNotFound`] suggesting the similar variables of `vars`
+pub fn variable_not_found(name: &str, location: Location, vars: &(impl Scope + ?Sized)) -> Error {
+    let location = location.suggest(name, vars.names());
+    Error::VariableNotFound(name.to_owned(), location)
+}
+
+/// Builds [`Error::FunctionNotImplemented`] suggesting the similar functions of `funcs`
+pub fn function_not_implemented(
+    name: &str,
+    location: Location,
+    funcs: &DashMap<Cow<'_, str>, Box<Function>>,
+) -> Error {
+    let location = location.suggest(name, funcs.iter().map(|f| f.key().to_string()));
+    Error::FunctionNotImplemented(name.to_owned(), location)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::builtin;
+    use crate::error::Location;
     use crate::parser::parser;
 
     use dashmap::DashMap;
@@ -122,8 +258,8 @@ mod tests {
         let tnodes = parser(template, "{{", "}}").unwrap();
         let mut res = String::new();
 
-        for tnode in tnodes.into_iter() {
-            let out = nodes(&mut res, tnode, &vars, &DashMap::new());
+        for tnode in tnodes.iter() {
+            let out = nodes(&mut res, tnode, template, &vars, &DashMap::new());
             assert!(out.is_ok());
         }
 
@@ -141,8 +277,8 @@ mod tests {
         let tnodes = parser(template, "{{", "}}").unwrap();
         let mut res = String::new();
 
-        for tnode in tnodes.into_iter() {
-            let out = nodes(&mut res, tnode, &vars, &funcs);
+        for tnode in tnodes.iter() {
+            let out = nodes(&mut res, tnode, template, &vars, &funcs);
             assert!(out.is_ok());
         }
 
@@ -166,8 +302,8 @@ mod tests {
         let tnodes = parser(template, "{{", "}}").unwrap();
         let mut res = String::new();
 
-        for node in tnodes.into_iter() {
-            let out = nodes(&mut res, node, &vars, &funcs);
+        for node in tnodes.iter() {
+            let out = nodes(&mut res, node, template, &vars, &funcs);
             assert!(out.is_ok());
         }
 
@@ -192,11 +328,148 @@ mod tests {
         let tnodes = parser(template, "{{", "}}").unwrap();
         let mut res = String::new();
 
-        for tnode in tnodes.into_iter() {
-            let out = nodes(&mut res, tnode, &vars, &funcs);
+        for tnode in tnodes.iter() {
+            let out = nodes(&mut res, tnode, template, &vars, &funcs);
             assert!(out.is_ok());
         }
 
         assert_eq!(&res, "Hello\nworld !");
     }
+
+    #[test]
+    fn suggest_similar_names() {
+        let vars = DashMap::from_iter([(Cow::Borrowed("name"), "World".to_string())]);
+        let funcs = DashMap::from_iter([(
+            Cow::Borrowed("toLower"),
+            Box::new(builtin::text::to_lower as Function),
+        )]);
+        let mut res = String::new();
+
+        let template = "{{ toLowercase(name) }}";
+        let tnode = &parser(template, "{{", "}}").unwrap()[0];
+        let Err(Error::FunctionNotImplemented(_, location)) =
+            nodes(&mut res, tnode, template, &vars, &funcs)
+        else {
+            panic!("expected FunctionNotImplemented");
+        };
+        assert_eq!(location.help, "help: did you mean \"toLower\"?");
+
+        let template = "{{ Name }}";
+        let tnode = &parser(template, "{{", "}}").unwrap()[0];
+        let Err(Error::VariableNotFound(_, location)) =
+            nodes(&mut res, tnode, template, &vars, &funcs)
+        else {
+            panic!("expected VariableNotFound");
+        };
+        assert_eq!(location.help, "help: did you mean \"name\"?");
+    }
+
+    #[test]
+    fn locate_names_not_sliced_from_template() {
+        let vars = DashMap::<Cow<str>, String>::new();
+        let funcs = DashMap::new();
+        let template = "Hi {{ upper(name) }}";
+        let tnode =
+            TemplateNode::Function("upper", vec![TemplateNode::Variable("name", 12..16)], 6..17);
+        let mut errors = Vec::new();
+
+        node_recovering(&tnode, template, &vars, &funcs, &mut errors);
+
+        assert_eq!(
+            errors,
+            [
+                Error::FunctionNotImplemented("upper".to_owned(), Location::new(template, 6..11)),
+                Error::VariableNotFound("name".to_owned(), Location::new(template, 12..16)),
+            ]
+        );
+    }
+
+    #[test]
+    fn overlay_variables() {
+        let vars = DashMap::from_iter([
+            (Cow::Borrowed("name"), "World".to_string()),
+            (Cow::Borrowed("greeting"), "Hello".to_string()),
+        ]);
+        let extra = HashMap::from_iter([(Cow::Borrowed("name"), "Ana".to_string())]);
+        let overlay = Overlay {
+            base: &vars,
+            extra: &extra,
+        };
+        let template = "{{ greeting }} {{ name }}";
+        let mut res = String::new();
+
+        for tnode in &parser(template, "{{", "}}").unwrap() {
+            nodes(&mut res, tnode, template, &overlay, &DashMap::new()).unwrap();
+        }
+
+        assert_eq!(&res, "Hello Ana");
+        assert_eq!(vars.get("name").unwrap().as_str(), "World");
+
+        let mut names = overlay.names();
+        names.sort();
+        assert_eq!(names, ["greeting", "name"]);
+    }
+
+    #[test]
+    fn render_collecting_errors() {
+        let vars = DashMap::from_iter([(Cow::Borrowed("var"), "WoRlD".to_string())]);
+        let funcs = DashMap::from_iter([(
+            Cow::Borrowed("toLower"),
+            Box::new(builtin::text::to_lower as Function),
+        )]);
+        let template = "{{ toLower(var) }} {{ missing }}\n{{ upper(var, other) }}!";
+        let mut res = String::new();
+        let mut errors = Vec::new();
+
+        for tnode in &parser(template, "{{", "}}").unwrap() {
+            nodes_recovering(&mut res, tnode, template, &vars, &funcs, &mut errors);
+        }
+
+        assert_eq!(&res, "world \n!");
+        assert_eq!(
+            errors,
+            [
+                Error::VariableNotFound("missing".to_owned(), Location::new(template, 22..29)),
+                Error::FunctionNotImplemented("upper".to_owned(), Location::new(template, 36..41)),
+                Error::VariableNotFound("other".to_owned(), Location::new(template, 47..52)),
+            ]
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            concat!(
+                "\x1b[1;91mError:\x1b[0m Function not implemented: upper\n",
+                " \x1b[1;34m-->\x1b[0m 2:4\n",
+                "\x1b[1;34m 2 | \x1b[0m{{ upper(var, other) }}!\n",
+                "        \x1b[1;91m^^^^^\x1b[0m\n",
+            )
+        );
+    }
+
+    #[test]
+    fn function_error_location() {
+        let funcs = DashMap::from_iter([(
+            Cow::Borrowed("trim"),
+            Box::new(builtin::text::trim as Function),
+        )]);
+        let template = "Hi\n{{ trim() }} and {{ trim() }}";
+        let mut res = String::new();
+        let tnodes = parser(template, "{{", "}}").unwrap();
+
+        let error = tnodes
+            .iter()
+            .find_map(|tnode| nodes(&mut res, tnode, template, &DashMap::new(), &funcs).err());
+
+        assert!(matches!(
+            error,
+            Some(Error::Function(
+                _,
+                Location {
+                    line: 2,
+                    column: 4,
+                    len: 6,
+                    ..
+                }
+            ))
+        ));
+    }
 }
@@ -2,21 +2,37 @@ use dashmap::DashMap;
 #[cfg(feature = "math")]
 use paste::paste;
 use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
-use crate::error::Error;
-use crate::parser::parser;
-use crate::render::nodes;
+use crate::ast::{ParsedTemplate, TemplateNode};
+use crate::error::{Error, Location};
+use crate::parser::{parser, parser_recovering};
+use crate::render::{
+    function_not_implemented, nodes, nodes_recovering, variable_not_found, Overlay, Scope,
+};
 use crate::{builtin, Variable};
 
 #[cfg(feature = "math")]
 use crate::gen_math_use;
 
-use self::function::FuncResult;
+use self::function::{FuncResult, FunctionSignature};
 
 pub mod function;
+#[cfg(any(
+    feature = "json",
+    feature = "toml",
+    feature = "yaml",
+    feature = "dotenv"
+))]
+mod loader;
+mod reference;
+#[cfg(feature = "serde")]
+mod ser;
 pub mod validations;
 
+pub use reference::{Reference, ReferenceKind};
+
 /// This corresponds to the type for custom functions that may exist.
 pub type Function = fn(&[String]) -> FuncResult;
 
@@ -38,8 +54,9 @@ pub type Function = fn(&[String]) -> FuncResult;
 pub struct SrTemplate<'a> {
     delimiter_start: Cow<'a, str>,
     delimiter_close: Cow<'a, str>,
-    variables: Arc<DashMap<Cow<'a, str>, String>>,
+    pub(crate) variables: Arc<DashMap<Cow<'a, str>, String>>,
     functions: Arc<DashMap<Cow<'a, str>, Box<Function>>>,
+    signatures: Arc<DashMap<Cow<'a, str>, FunctionSignature>>,
 }
 
 impl<'a> SrTemplate<'a> {
@@ -63,6 +80,99 @@ impl<'a> SrTemplate<'a> {
         });
     }
 
+    /// Adds the variables of a value under a custom prefix, instead of the one of its type
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix`: Prefix for all the variables, like `sender` in `sender.name`
+    /// * `value`: The value whose [`Variable::fields`] are added
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use srtemplate::{SrTemplate, Variable};
+    ///
+    /// struct User(&'static str);
+    ///
+    /// impl<'a> Variable<'a> for User {
+    ///     fn variables(&self) -> impl Iterator<Item = (Cow<'a, str>, String)> {
+    ///         [("User.name".into(), self.0.to_owned())].into_iter()
+    ///     }
+    /// }
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.add_as("sender", User("Ana"));
+    /// ctx.add_as("recipient", User("Luis"));
+    ///
+    /// assert_eq!(
+    ///     ctx.render("{{ sender.name }} to {{ recipient.name }}").unwrap(),
+    ///     "Ana to Luis"
+    /// );
+    /// ```
+    pub fn add_as<U: Into<Cow<'a, str>>, V: Variable<'a>>(&self, prefix: U, value: V) {
+        let prefix = prefix.into();
+        value.fields().for_each(|(name, value)| {
+            self.add_variable(crate::join_name(prefix.clone(), name), value);
+        });
+    }
+
+    /// Adds the variables of a value without prefix, like `name` instead of `User.name`
+    ///
+    /// The value itself, like the variant of an enum, has no name without the prefix,
+    /// so it is not added.
+    ///
+    /// # Arguments
+    ///
+    /// * `value`: The value whose [`Variable::fields`] are added
+    pub fn add_flat<V: Variable<'a>>(&self, value: V) {
+        value
+            .fields()
+            .filter(|(name, _)| !name.is_empty())
+            .for_each(|(name, value)| self.add_variable(name, value));
+    }
+
+    /// Adds any [`serde::Serialize`] value as variables that can later be rendered in the template
+    ///
+    /// Nested structs and maps are added as dotted names (`order.customer.name`),
+    /// sequences and tuples by index (`order.items.0`) and enum variants by their name
+    /// (`order.status.Shipped.carrier`). Unit variants are rendered as their name
+    /// and `None` values are omitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: Prefix for all the variables, an empty prefix adds the fields as top-level variables
+    /// * `value`: The value to be walked into variables
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use srtemplate::SrTemplate;
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.add_serialize("order", &HashMap::from([("id", 7)])).unwrap();
+    ///
+    /// assert_eq!(ctx.render("Order #{{ order.id }}").unwrap(), "Order #7");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Serialize`] if a map key cannot be represented as a string
+    /// or the value's `Serialize` implementation fails.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    #[cfg(feature = "serde")]
+    pub fn add_serialize<T: serde::Serialize + ?Sized>(
+        &self,
+        name: &str,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::to_variables(name, value)?
+            .into_iter()
+            .for_each(|(name, value)| self.add_variable(name, value));
+        Ok(())
+    }
+
     /// Adds variable that can later be rendered in the template
     ///
     /// # Arguments
@@ -99,12 +209,132 @@ impl<'a> SrTemplate<'a> {
     /// * `name`: Function name, this name is the one you will use in the template
     /// * `func`: This is the function that will be evaluated when it is called from the template
     pub fn add_function<T: Into<Cow<'a, str>>>(&self, name: T, func: Function) {
+        let name = name.into();
+        self.signatures.remove(&name);
         self.functions
-            .entry(name.into())
-            .and_modify(|old| *old = Box::new(func.clone()))
+            .entry(name)
+            .and_modify(|old| **old = func)
             .or_insert_with(|| Box::new(func));
     }
 
+    /// Adds function that can later be rendered in the template, along with its signature
+    ///
+    /// The signature is listed by [`SrTemplate::functions`] and used by [`SrTemplate::validate`]
+    /// to check the number of arguments of each call. Functions declared with `#[function]`
+    /// generate their signature in a `<name>_signature` function, and a `register_<name>`
+    /// function that adds both with the name of the Rust function.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: Function name, this name is the one you will use in the template
+    /// * `func`: This is the function that will be evaluated when it is called from the template
+    /// * `signature`: The signature of the function, its name is replaced by `name`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::prelude::{FuncResult, FunctionSignature};
+    /// use srtemplate::SrTemplate;
+    ///
+    /// fn repeat(args: &[String]) -> FuncResult {
+    ///     let times = args.get(1).and_then(|n| n.parse().ok()).unwrap_or(2);
+    ///     Ok(args[0].repeat(times))
+    /// }
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.add_function_with_signature(
+    ///     "repeat",
+    ///     repeat,
+    ///     FunctionSignature::new("repeat")
+    ///         .param("text", "String")
+    ///         .optional_param("times", "usize")
+    ///         .description("Repeats a text"),
+    /// );
+    ///
+    /// assert_eq!(ctx.render("{{ repeat(\"ab\") }}").unwrap(), "abab");
+    /// assert!(ctx.validate("{{ repeat(\"ab\", 2, 3) }}").is_err());
+    /// ```
+    pub fn add_function_with_signature<T: Into<Cow<'a, str>>>(
+        &self,
+        name: T,
+        func: Function,
+        mut signature: FunctionSignature,
+    ) {
+        let name = name.into();
+        self.add_function(name.clone(), func);
+        name.as_ref().clone_into(&mut signature.name);
+        self.signatures.insert(name, signature);
+    }
+
+    /// Adds functions that can later be rendered in the template, along with their signatures
+    ///
+    /// # Arguments
+    ///
+    /// * `values`: The name, function and signature of each function,
+    ///   see [`SrTemplate::add_function_with_signature`]
+    pub fn add_functions_with_signatures<
+        U: Into<Cow<'a, str>>,
+        V: Iterator<Item = (U, Function, FunctionSignature)>,
+    >(
+        &self,
+        values: V,
+    ) {
+        values.for_each(|(name, func, signature)| {
+            self.add_function_with_signature(name, func, signature);
+        });
+    }
+
+    /// Returns the signature of a registered function, if it was added with one
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the function.
+    pub fn signature<T: Into<Cow<'a, str>>>(&self, name: T) -> Option<FunctionSignature> {
+        self.signatures
+            .get(&name.into())
+            .map(|signature| signature.value().clone())
+    }
+
+    /// Lists the registered functions sorted by name, with their signature if they have one.
+    ///
+    /// This is meant for tools, like generating the documentation of the functions
+    /// available to template authors or autocompleting them in an editor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::prelude::FunctionSignature;
+    /// use srtemplate::SrTemplate;
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.clear_functions();
+    /// ctx.add_function("b", |_| Ok(String::new()));
+    /// ctx.add_function_with_signature(
+    ///     "a",
+    ///     |args| Ok(args.concat()),
+    ///     FunctionSignature::new("a").variadic_param("values", "String"),
+    /// );
+    ///
+    /// let functions = ctx.functions();
+    /// assert_eq!(functions[0].0, "a");
+    /// assert_eq!(functions[0].1.as_ref().unwrap().to_string(), "a(...values: String)");
+    /// assert_eq!(functions[1], ("b".into(), None));
+    /// ```
+    pub fn functions(&self) -> Vec<(Cow<'a, str>, Option<FunctionSignature>)> {
+        let mut functions = self
+            .functions
+            .iter()
+            .map(|func| {
+                let name = func.key().clone();
+                let signature = self.signature(name.clone());
+                (name, signature)
+            })
+            .collect::<Vec<_>>();
+        functions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        functions
+    }
+
     /// Adds functions that can later be rendered in the template
     ///
     /// # Arguments
@@ -162,7 +392,9 @@ impl<'a> SrTemplate<'a> {
     ///
     /// * `name` - The name of the function to remove.
     pub fn remove_function<T: Into<Cow<'a, str>>>(&self, name: T) {
-        self.functions.remove(&name.into());
+        let name = name.into();
+        self.functions.remove(&name);
+        self.signatures.remove(&name);
     }
 
     /// Clears all variables from the template string.
@@ -173,6 +405,7 @@ impl<'a> SrTemplate<'a> {
     /// Clears all functions from the template string.
     pub fn clear_functions(&self) {
         self.functions.clear();
+        self.signatures.clear();
     }
 
     /// Sets the delimiters for the template string.
@@ -190,6 +423,183 @@ impl<'a> SrTemplate<'a> {
         self.delimiter_close = close.into();
     }
 
+    /// Lists the variables and functions referenced by a template, without rendering it.
+    ///
+    /// The references are returned in the order they appear in the template,
+    /// including the ones used as function arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A template string to be inspected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::{ReferenceKind, SrTemplate};
+    ///
+    /// let ctx = SrTemplate::default();
+    /// let refs = ctx.references("Hi {{ toUpper(name) }}").unwrap();
+    ///
+    /// assert_eq!(refs[0].kind, ReferenceKind::Function);
+    /// assert_eq!(refs[1].name, "name");
+    /// assert_eq!((refs[1].line, refs[1].column), (1, 15));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the syntax of the template is invalid.
+    pub fn references<'t>(&self, text: &'t str) -> Result<Vec<Reference<'t>>, Error> {
+        let tnodes = parser(
+            text,
+            self.delimiter_start.as_ref(),
+            self.delimiter_close.as_ref(),
+        )?;
+        let mut refs = Vec::new();
+        reference::collect(text, &tnodes, &mut refs);
+
+        Ok(refs)
+    }
+
+    /// Lists the references of a template like [`SrTemplate::references`], but instead of
+    /// stopping at the first syntax error every error is collected.
+    ///
+    /// The expressions with invalid syntax are skipped, the references of the rest are returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A template string to be inspected.
+    ///
+    /// # Returns
+    ///
+    /// The references and an [`Error::BadSyntax`] for each syntax error,
+    /// both in the order they appear in the template.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::SrTemplate;
+    ///
+    /// let ctx = SrTemplate::default();
+    /// let (refs, errors) = ctx.references_recovering("{{ a }} {{ trim( }} {{ b }} {{ 1x( }}");
+    ///
+    /// assert_eq!(refs.iter().map(|r| r.name).collect::<Vec<_>>(), ["a", "b"]);
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    pub fn references_recovering<'t>(&self, text: &'t str) -> (Vec<Reference<'t>>, Vec<Error>) {
+        let (tnodes, errors) = parser_recovering(
+            text,
+            self.delimiter_start.as_ref(),
+            self.delimiter_close.as_ref(),
+        );
+        let mut refs = Vec::new();
+        reference::collect(text, &tnodes, &mut refs);
+
+        (refs, errors)
+    }
+
+    /// Returns the set of variable names referenced by a template, without rendering it.
+    ///
+    /// This includes the variables used as function arguments, see [`SrTemplate::references`]
+    /// to also get the location of each one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the syntax of the template is invalid.
+    pub fn referenced_variables<'t>(&self, text: &'t str) -> Result<BTreeSet<&'t str>, Error> {
+        self.referenced_names(text, ReferenceKind::Variable)
+    }
+
+    /// Returns the set of function names called by a template, without rendering it.
+    ///
+    /// This can be used to reject templates that use functions that are not allowed,
+    /// see [`SrTemplate::references`] to also get the location of each one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the syntax of the template is invalid.
+    pub fn referenced_functions<'t>(&self, text: &'t str) -> Result<BTreeSet<&'t str>, Error> {
+        self.referenced_names(text, ReferenceKind::Function)
+    }
+
+    fn referenced_names<'t>(
+        &self,
+        text: &'t str,
+        kind: ReferenceKind,
+    ) -> Result<BTreeSet<&'t str>, Error> {
+        Ok(self
+            .references(text)?
+            .into_iter()
+            .filter(|r| r.kind == kind)
+            .map(|r| r.name)
+            .collect())
+    }
+
+    /// Validates a template against the current variables and functions, without rendering it.
+    ///
+    /// Checks the syntax of the template and that every referenced variable exists
+    /// and every called function is registered. No function is called,
+    /// so side effects (like reading the environment) never happen during validation.
+    /// The number of arguments is only checked for functions added with a signature,
+    /// see [`SrTemplate::add_function_with_signature`], the others check it when rendering.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A template string to be validated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::{Error, SrTemplate};
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.add_variable("name", "World");
+    ///
+    /// assert!(ctx.validate("Hello {{ toUpper(name) }}").is_ok());
+    ///
+    /// let errors = ctx.validate("Hello {{ toUpperCase(nme) }}").unwrap_err();
+    /// assert!(matches!(&errors[0], Error::FunctionNotImplemented(name, _) if name == "toUpperCase"));
+    /// assert!(matches!(&errors[1], Error::VariableNotFound(name, _) if name == "nme"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns every error found, in the order they appear in the template:
+    /// - [`Error::BadSyntax`] for each expression with invalid syntax, the rest of the
+    ///   template is still validated.
+    /// - [`Error::VariableNotFound`] for each reference to a variable that does not exist.
+    /// - [`Error::FunctionNotImplemented`] for each call to a function that is not registered.
+    /// - [`Error::Function`] with [`FunctionError::ArgumentsIncomplete`](function::Error::ArgumentsIncomplete)
+    ///   for each call with a number of arguments that does not match the signature of the function.
+    pub fn validate<T: AsRef<str>>(&self, text: T) -> Result<(), Vec<Error>> {
+        let text = text.as_ref();
+        let (refs, mut errors) = self.references_recovering(text);
+
+        errors.extend(refs.into_iter().filter_map(|r| match r.kind {
+            ReferenceKind::Variable if !self.variables.contains_key(r.name) => Some(
+                variable_not_found(r.name, Location::new(text, r.span), self.variables.as_ref()),
+            ),
+            ReferenceKind::Function if !self.functions.contains_key(r.name) => Some(
+                function_not_implemented(r.name, Location::new(text, r.span), &self.functions),
+            ),
+            ReferenceKind::Function => {
+                let signature = self.signatures.get(r.name)?;
+                signature
+                    .check_args(r.args.unwrap_or_default())
+                    .err()
+                    .map(|e| Error::Function(e, Location::new(text, r.span)))
+            }
+            ReferenceKind::Variable => None,
+        }));
+        // keep the errors in the order they appear in the template
+        errors.sort_by_key(error_offset);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Renders a template by replacing variables and processing functions.
     ///
     /// # Arguments
@@ -221,24 +631,217 @@ impl<'a> SrTemplate<'a> {
     /// - The syntax of the template is invalid.
     /// - A variable or function is not found or fails during processing.
     pub fn render<T: AsRef<str>>(&self, text: T) -> Result<String, Error> {
+        self.render_with_variables(text.as_ref(), self.variables.as_ref())
+    }
+
+    /// Renders a template that is already parsed, without parsing it again.
+    ///
+    /// The template keeps the delimiters it was parsed with, so they can be different from
+    /// the delimiters of this context.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - A template parsed with [`ParsedTemplate::parse`], or at compile time
+    ///   with the `template!` and `include_template!` macros.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::ast::ParsedTemplate;
+    /// use srtemplate::SrTemplate;
+    ///
+    /// let ctx = SrTemplate::default();
+    /// let template = ParsedTemplate::parse("Hello, {{ name }}!", "{{", "}}").unwrap();
+    ///
+    /// for name in ["World", "Rust"] {
+    ///     ctx.add_variable("name", name);
+    ///     assert_eq!(ctx.render_parsed(&template).unwrap(), format!("Hello, {name}!"));
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable or function is not found or fails during processing.
+    pub fn render_parsed(&self, template: &ParsedTemplate) -> Result<String, Error> {
+        self.render_nodes(template.source(), template.nodes(), self.variables.as_ref())
+    }
+
+    /// Renders a template that is already parsed, with some variables added only for this render.
+    ///
+    /// The variables are not stored in the context, so it can be shared between renders
+    /// of different values. They take precedence over the variables of the context with the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - A template parsed with [`ParsedTemplate::parse`], or at compile time
+    ///   with the `template!` and `include_template!` macros.
+    /// * `variables` - Names and values of the variables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::ast::ParsedTemplate;
+    /// use srtemplate::SrTemplate;
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.add_variable("greeting", "Hello");
+    /// let template = ParsedTemplate::parse("{{ greeting }}, {{ name }}!", "{{", "}}").unwrap();
+    ///
+    /// assert_eq!(
+    ///     ctx.render_parsed_with(&template, [("name", "World")]).unwrap(),
+    ///     "Hello, World!"
+    /// );
+    /// assert!(!ctx.contains_variable("name"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable or function is not found or fails during processing.
+    pub fn render_parsed_with<U: Into<Cow<'a, str>>, T: ToString>(
+        &self,
+        template: &ParsedTemplate,
+        variables: impl IntoIterator<Item = (U, T)>,
+    ) -> Result<String, Error> {
+        let extra = variables
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        let variables = Overlay {
+            base: self.variables.as_ref(),
+            extra: &extra,
+        };
+
+        self.render_nodes(template.source(), template.nodes(), &variables)
+    }
+
+    /// Renders a template without stopping at the first error.
+    ///
+    /// Syntax errors are collected and the parser continues at the next delimiter,
+    /// missing variables, missing functions and function errors are collected and the rendering
+    /// continues. Every part of the template that failed renders nothing.
+    ///
+    /// This is useful for editors, where all the mistakes of a template can be shown at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A template string to be rendered.
+    ///
+    /// # Returns
+    ///
+    /// The partially rendered template and the errors found, in the order they appear.
+    /// If there are no errors the output is the same as [`SrTemplate::render`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::SrTemplate;
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.add_variable("name", "World");
+    ///
+    /// let (output, errors) = ctx.render_recovering("Hi {{ name }}{{ nme }}{{ trim( }}!");
+    /// assert_eq!(output, "Hi World!");
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    pub fn render_recovering<T: AsRef<str>>(&self, text: T) -> (String, Vec<Error>) {
         let input = text.as_ref();
-        let open_delim = self.delimiter_start.as_ref();
-        let close_delim = self.delimiter_close.as_ref();
         let mut res = String::with_capacity(input.len());
-        let tnodes = parser(input, open_delim, close_delim)?;
+        let (tnodes, mut errors) = parser_recovering(
+            input,
+            self.delimiter_start.as_ref(),
+            self.delimiter_close.as_ref(),
+        );
+        let mut render_errors = Vec::new();
 
-        for var in tnodes {
-            nodes(
+        for var in &tnodes {
+            nodes_recovering(
                 &mut res,
                 var,
+                input,
                 self.variables.as_ref(),
                 self.functions.as_ref(),
-            )?;
+                &mut render_errors,
+            );
+        }
+
+        // keep the errors in the order they appear in the template
+        errors.extend(render_errors);
+        errors.sort_by_key(error_offset);
+
+        (res, errors)
+    }
+
+    /// Renders a template with a [`serde::Serialize`] value added only for this render.
+    ///
+    /// The value is walked the same way as [`SrTemplate::add_serialize`], but the variables
+    /// are not stored in the context, so it can be shared between renders of different values.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A template string to be rendered.
+    /// * `name`: Prefix for the variables of the value
+    /// * `value`: The value to be walked into variables
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The value cannot be serialized.
+    /// - The syntax of the template is invalid.
+    /// - A variable or function is not found or fails during processing.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    #[cfg(feature = "serde")]
+    pub fn render_with_serialize<T: AsRef<str>, S: serde::Serialize + ?Sized>(
+        &self,
+        text: T,
+        name: &str,
+        value: &S,
+    ) -> Result<String, Error> {
+        let extra = ser::to_variables(name, value)?
+            .into_iter()
+            .map(|(name, value)| (Cow::Owned(name), value))
+            .collect::<HashMap<_, _>>();
+        let variables = Overlay {
+            base: self.variables.as_ref(),
+            extra: &extra,
+        };
+
+        self.render_with_variables(text.as_ref(), &variables)
+    }
+
+    fn render_with_variables(&self, input: &str, variables: &impl Scope) -> Result<String, Error> {
+        let open_delim = self.delimiter_start.as_ref();
+        let close_delim = self.delimiter_close.as_ref();
+        let tnodes = parser(input, open_delim, close_delim)?;
+
+        self.render_nodes(input, &tnodes, variables)
+    }
+
+    fn render_nodes(
+        &self,
+        input: &str,
+        tnodes: &[TemplateNode],
+        variables: &impl Scope,
+    ) -> Result<String, Error> {
+        let mut res = String::with_capacity(input.len());
+
+        for var in tnodes {
+            nodes(&mut res, var, input, variables, self.functions.as_ref())?;
         }
         Ok(res)
     }
 }
 
+/// Byte offset of the error in the template, errors without location go last
+fn error_offset(error: &Error) -> usize {
+    match error {
+        Error::BadSyntax(e) => e.at,
+        Error::VariableNotFound(_, location)
+        | Error::FunctionNotImplemented(_, location)
+        | Error::Function(_, location) => location.at,
+        _ => usize::MAX,
+    }
+}
+
 impl Default for SrTemplate<'_> {
     /// Generates an instance with all the builtin functions that are enabled from features
     fn default() -> Self {
@@ -247,17 +850,47 @@ impl Default for SrTemplate<'_> {
             delimiter_close: "}}".into(),
             variables: Arc::default(),
             functions: Arc::default(),
+            signatures: Arc::default(),
         };
 
         #[cfg(feature = "os")]
-        tmp.add_function("env", builtin::os::env);
+        tmp.add_function_with_signature(
+            "env",
+            builtin::os::env,
+            FunctionSignature::new("env")
+                .param("name", "String")
+                .variadic_param("names", "String")
+                .description("Values of the environment variables, separated by space"),
+        );
 
         #[cfg(feature = "text")]
-        {
-            tmp.add_function("toLower", builtin::text::to_lower);
-            tmp.add_function("toUpper", builtin::text::to_upper);
-            tmp.add_function("trim", builtin::text::trim);
-        }
+        tmp.add_functions_with_signatures(
+            [
+                (
+                    "toLower",
+                    builtin::text::to_lower as Function,
+                    "Texts converted to lowercase, separated by space",
+                ),
+                (
+                    "toUpper",
+                    builtin::text::to_upper,
+                    "Texts converted to uppercase, separated by space",
+                ),
+                (
+                    "trim",
+                    builtin::text::trim,
+                    "Texts without leading and trailing whitespace, separated by space",
+                ),
+            ]
+            .into_iter()
+            .map(|(name, func, description)| {
+                let signature = FunctionSignature::new(name)
+                    .param("text", "String")
+                    .variadic_param("texts", "String")
+                    .description(description);
+                (name, func, signature)
+            }),
+        );
 
         #[cfg(feature = "math")]
         {
@@ -267,3 +900,151 @@ impl Default for SrTemplate<'_> {
         tmp
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::prelude::{FunctionError, FunctionSignature};
+    use crate::{join_name, Error, Location, SrTemplate, Variable};
+
+    struct Point(u8, u8);
+
+    impl<'a> Variable<'a> for Point {
+        fn variables(&self) -> impl Iterator<Item = (Cow<'a, str>, String)> {
+            [
+                ("point.x".into(), self.0.to_string()),
+                ("point.y".into(), self.1.to_string()),
+            ]
+            .into_iter()
+        }
+    }
+
+    #[test]
+    fn default_variable_fields() {
+        let fields = Point(1, 2).fields().collect::<Vec<_>>();
+
+        assert_eq!(
+            fields,
+            [("x".into(), "1".to_owned()), ("y".into(), "2".to_owned())]
+        );
+        assert_eq!(join_name("line.start", "x".into()), "line.start.x");
+        assert_eq!(join_name("line.start", "".into()), "line.start");
+        assert_eq!(join_name("", "x".into()), "x");
+    }
+
+    #[test]
+    fn custom_prefixes() {
+        let ctx = SrTemplate::default();
+        ctx.add_as("start", Point(1, 2));
+        ctx.add_as("", Point(3, 4));
+
+        assert_eq!(ctx.render("{{ start.x }},{{ start.y }}").unwrap(), "1,2");
+        assert_eq!(ctx.render("{{ x }},{{ y }}").unwrap(), "3,4");
+
+        ctx.add_flat(Point(5, 6));
+        assert_eq!(ctx.render("{{ x }},{{ y }}").unwrap(), "5,6");
+        assert!(ctx.render("{{ point.x }}").is_err());
+    }
+
+    #[test]
+    fn validate_collects_errors() {
+        let ctx = SrTemplate::default();
+        ctx.add_variable("name", "World");
+
+        let template = "{{ missing }} {{ name }} {{ toLower(unknown(name), other) }}";
+
+        assert_eq!(
+            ctx.validate(template),
+            Err(vec![
+                Error::VariableNotFound("missing".to_owned(), Location::new(template, 3..10)),
+                Error::FunctionNotImplemented(
+                    "unknown".to_owned(),
+                    Location::new(template, 36..43)
+                ),
+                Error::VariableNotFound("other".to_owned(), Location::new(template, 51..56)),
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_does_not_call_functions() {
+        let ctx = SrTemplate::default();
+        ctx.add_function("fail", |_| panic!("functions must not be called"));
+
+        assert_eq!(ctx.validate("{{ fail() }}"), Ok(()));
+    }
+
+    #[test]
+    fn validate_arguments() {
+        let ctx = SrTemplate::default();
+        ctx.add_function_with_signature(
+            "pad",
+            |args| Ok(args.concat()),
+            FunctionSignature::new("other")
+                .param("text", "String")
+                .optional_param("width", "usize"),
+        );
+        ctx.add_function("any", |_| Ok(String::new()));
+
+        let template = "{{ pad() }} {{ pad(1) }} {{ pad(1, 2, 3) }} {{ any(1, 2, 3) }}";
+
+        assert_eq!(
+            ctx.validate(template),
+            Err(vec![
+                Error::Function(
+                    FunctionError::ArgumentsIncomplete(0, 1),
+                    Location::new(template, 3..6)
+                ),
+                Error::Function(
+                    FunctionError::ArgumentsIncomplete(3, 2),
+                    Location::new(template, 28..31)
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn function_signatures() {
+        let ctx = SrTemplate::default();
+        ctx.add_function_with_signature(
+            "pad",
+            |args| Ok(args.concat()),
+            FunctionSignature::new("other").param("text", "String"),
+        );
+
+        assert_eq!(ctx.signature("pad").unwrap().name, "pad");
+        assert_eq!(ctx.signature("toLower").unwrap().min_args(), 1);
+        assert!(ctx.functions().windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        ctx.add_function("pad", |_| Ok(String::new()));
+        assert_eq!(ctx.signature("pad"), None);
+        assert!(ctx.functions().contains(&("pad".into(), None)));
+
+        ctx.remove_function("toLower");
+        assert_eq!(ctx.signature("toLower"), None);
+        ctx.clear_functions();
+        assert!(ctx.functions().is_empty());
+    }
+
+    #[test]
+    fn validate_bad_syntax() {
+        let ctx = SrTemplate::default();
+        let errors = ctx.validate("{{ name ").unwrap_err();
+
+        assert!(matches!(errors.as_slice(), [Error::BadSyntax(_)]));
+
+        let template = "{{ trim( }} {{ missing }} {{ 1x( }} {{ toLower(a) }}";
+        let errors = ctx.validate(template).unwrap_err();
+
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                Error::BadSyntax(_),
+                Error::VariableNotFound(name, _),
+                Error::BadSyntax(_),
+                Error::VariableNotFound(arg, _),
+            ] if name == "missing" && arg == "a"
+        ));
+    }
+}
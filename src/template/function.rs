@@ -18,6 +18,167 @@ pub enum Error {
     #[error("This function require {0} arguments, but found {1}")]
     ArgumentsIncomplete(usize, usize),
 
+    #[error(transparent)]
+    ParseArgument(Box<ParseArgumentError>),
+
     #[error("Error calling the function: {0}")]
     RuntimeError(String),
 }
+
+/// An argument of a function that cannot be parsed into the type of its parameter
+#[derive(Clone, Debug, Error, PartialEq)]
+#[error("Argument `{param}` of `{function}` must be {ty}, but found {value:?}")]
+pub struct ParseArgumentError {
+    /// Name of the function
+    pub function: String,
+    /// Name of the parameter
+    pub param: String,
+    /// The argument received
+    pub value: String,
+    /// Name of the type of the parameter
+    pub ty: String,
+}
+
+/// Signature of a registered function, used to document it and to check the number
+/// of arguments of its calls without calling it
+///
+/// # Example
+///
+/// ```
+/// use srtemplate::prelude::FunctionSignature;
+///
+/// let signature = FunctionSignature::new("repeat")
+///     .param("text", "String")
+///     .optional_param("times", "u8")
+///     .description("Repeats a text");
+///
+/// assert_eq!(signature.min_args(), 1);
+/// assert_eq!(signature.max_args(), Some(2));
+/// assert_eq!(signature.to_string(), "repeat(text: String, times?: u8)");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FunctionSignature {
+    /// Name of the function in the template
+    pub name: String,
+    /// Parameters of the function, in order
+    pub params: Vec<FunctionParam>,
+    /// Description of what the function does
+    pub description: Option<String>,
+}
+
+/// A parameter of a [`FunctionSignature`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FunctionParam {
+    /// Name of the parameter
+    pub name: String,
+    /// Name of the type the argument is parsed into
+    pub ty: String,
+    /// Whether the argument can be omitted
+    pub optional: bool,
+    /// Whether the parameter takes the rest of the arguments
+    pub variadic: bool,
+}
+
+impl FunctionSignature {
+    /// Creates a signature without parameters nor description
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: Name of the function in the template
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a parameter whose argument must be given
+    #[must_use]
+    pub fn param<N: Into<String>, T: Into<String>>(self, name: N, ty: T) -> Self {
+        self.with_param(name, ty, false, false)
+    }
+
+    /// Adds a parameter whose argument can be omitted
+    #[must_use]
+    pub fn optional_param<N: Into<String>, T: Into<String>>(self, name: N, ty: T) -> Self {
+        self.with_param(name, ty, true, false)
+    }
+
+    /// Adds a parameter that takes the rest of the arguments, which can be none
+    #[must_use]
+    pub fn variadic_param<N: Into<String>, T: Into<String>>(self, name: N, ty: T) -> Self {
+        self.with_param(name, ty, true, true)
+    }
+
+    /// Sets the description of the function
+    #[must_use]
+    pub fn description<T: Into<String>>(mut self, description: T) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Minimum number of arguments of a call
+    pub fn min_args(&self) -> usize {
+        self.params.iter().filter(|param| !param.optional).count()
+    }
+
+    /// Maximum number of arguments of a call, `None` if the function is variadic
+    pub fn max_args(&self) -> Option<usize> {
+        (!self.params.iter().any(|param| param.variadic)).then_some(self.params.len())
+    }
+
+    /// Checks the number of arguments of a call against the signature
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ArgumentsIncomplete`] with the number of arguments found
+    /// and the number expected if there are too few or too many.
+    pub fn check_args(&self, len: usize) -> Result<(), Error> {
+        let min = self.min_args();
+        if len < min {
+            return Err(Error::ArgumentsIncomplete(len, min));
+        }
+        match self.max_args() {
+            Some(max) if len > max => Err(Error::ArgumentsIncomplete(len, max)),
+            _ => Ok(()),
+        }
+    }
+
+    fn with_param<N: Into<String>, T: Into<String>>(
+        mut self,
+        name: N,
+        ty: T,
+        optional: bool,
+        variadic: bool,
+    ) -> Self {
+        self.params.push(FunctionParam {
+            name: name.into(),
+            ty: ty.into(),
+            optional,
+            variadic,
+        });
+        self
+    }
+}
+
+impl std::fmt::Display for FunctionSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (idx, param) in self.params.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            if param.variadic {
+                f.write_str("...")?;
+            }
+            f.write_str(&param.name)?;
+            if param.optional && !param.variadic {
+                f.write_str("?")?;
+            }
+            write!(f, ": {}", param.ty)?;
+        }
+        f.write_str(")")
+    }
+}
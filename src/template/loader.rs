@@ -0,0 +1,466 @@
+use std::path::Path;
+
+use crate::error::Error;
+
+use super::SrTemplate;
+
+/// Name used in errors when the variables are not loaded from a file
+const STR_SOURCE: &str = "<string>";
+
+impl SrTemplate<'_> {
+    /// Loads the variables of a JSON file.
+    ///
+    /// Objects and arrays are added as dotted names (`server.hosts.0`) and `null` values are omitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path of the JSON file
+    /// * `prefix`: Prefix for all the variables, an empty prefix adds them as top-level variables
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LoadVariables`] if the file cannot be read or is not valid JSON.
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[cfg(feature = "json")]
+    pub fn load_variables_json<P: AsRef<Path>>(&self, path: P, prefix: &str) -> Result<(), Error> {
+        let (file, content) = read_file(path.as_ref())?;
+        self.load_json(&file, &content, prefix)
+    }
+
+    /// Loads the variables of a JSON string.
+    ///
+    /// See [`SrTemplate::load_variables_json`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::SrTemplate;
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.load_variables_json_str(r#"{ "user": { "name": "Sergio" } }"#, "config").unwrap();
+    ///
+    /// assert_eq!(ctx.render("Hi {{ config.user.name }}").unwrap(), "Hi Sergio");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LoadVariables`] if the content is not valid JSON.
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[cfg(feature = "json")]
+    pub fn load_variables_json_str(&self, content: &str, prefix: &str) -> Result<(), Error> {
+        self.load_json(STR_SOURCE, content, prefix)
+    }
+
+    #[cfg(feature = "json")]
+    fn load_json(&self, file: &str, content: &str, prefix: &str) -> Result<(), Error> {
+        let value = serde_json::from_str::<serde_json::Value>(content)
+            .map_err(|e| load_error(file, None, e))?;
+        self.load_serialize(file, &value, prefix)
+    }
+
+    /// Loads the variables of a TOML file.
+    ///
+    /// Tables and arrays are added as dotted names (`server.hosts.0`)
+    /// and datetimes are added in their TOML representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path of the TOML file
+    /// * `prefix`: Prefix for all the variables, an empty prefix adds them as top-level variables
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LoadVariables`] if the file cannot be read or is not valid TOML.
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    #[cfg(feature = "toml")]
+    pub fn load_variables_toml<P: AsRef<Path>>(&self, path: P, prefix: &str) -> Result<(), Error> {
+        let (file, content) = read_file(path.as_ref())?;
+        self.load_toml(&file, &content, prefix)
+    }
+
+    /// Loads the variables of a TOML string.
+    ///
+    /// See [`SrTemplate::load_variables_toml`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LoadVariables`] if the content is not valid TOML.
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    #[cfg(feature = "toml")]
+    pub fn load_variables_toml_str(&self, content: &str, prefix: &str) -> Result<(), Error> {
+        self.load_toml(STR_SOURCE, content, prefix)
+    }
+
+    #[cfg(feature = "toml")]
+    fn load_toml(&self, file: &str, content: &str, prefix: &str) -> Result<(), Error> {
+        let mut value =
+            toml::from_str::<toml::Value>(content).map_err(|e| load_error(file, None, e))?;
+        toml_datetimes_to_string(&mut value);
+        self.load_serialize(file, &value, prefix)
+    }
+
+    /// Loads the variables of a YAML file.
+    ///
+    /// Mappings and sequences are added as dotted names (`server.hosts.0`) and `null` values are omitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path of the YAML file
+    /// * `prefix`: Prefix for all the variables, an empty prefix adds them as top-level variables
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LoadVariables`] if the file cannot be read, is not valid YAML
+    /// or contains a mapping key that is not a scalar.
+    #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+    #[cfg(feature = "yaml")]
+    pub fn load_variables_yaml<P: AsRef<Path>>(&self, path: P, prefix: &str) -> Result<(), Error> {
+        let (file, content) = read_file(path.as_ref())?;
+        self.load_yaml(&file, &content, prefix)
+    }
+
+    /// Loads the variables of a YAML string.
+    ///
+    /// See [`SrTemplate::load_variables_yaml`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LoadVariables`] if the content is not valid YAML
+    /// or contains a mapping key that is not a scalar.
+    #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+    #[cfg(feature = "yaml")]
+    pub fn load_variables_yaml_str(&self, content: &str, prefix: &str) -> Result<(), Error> {
+        self.load_yaml(STR_SOURCE, content, prefix)
+    }
+
+    #[cfg(feature = "yaml")]
+    fn load_yaml(&self, file: &str, content: &str, prefix: &str) -> Result<(), Error> {
+        let value = serde_yaml::from_str::<serde_yaml::Value>(content)
+            .map_err(|e| load_error(file, None, e))?;
+        self.load_serialize(file, &value, prefix)
+    }
+
+    #[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+    fn load_serialize<T: serde::Serialize>(
+        &self,
+        file: &str,
+        value: &T,
+        prefix: &str,
+    ) -> Result<(), Error> {
+        super::ser::collect(prefix, value)
+            .map_err(|(e, key)| load_error(file, Some(key), e))?
+            .into_iter()
+            .for_each(|(name, value)| self.add_variable(name, value));
+        Ok(())
+    }
+
+    /// Loads the variables of a `.env` file.
+    ///
+    /// Each `KEY=VALUE` line is added as a variable, empty lines and lines starting with `#` are ignored.
+    /// Keys may be preceded by `export`, values may be wrapped in double quotes (supporting
+    /// `\n`, `\t`, `\"` and `\\` escapes) or single quotes (taken literally),
+    /// and unquoted values end at an inline ` #` comment.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path of the `.env` file
+    /// * `prefix`: Prefix for all the variables, an empty prefix adds them as top-level variables
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LoadVariables`] if the file cannot be read or has a malformed line.
+    #[cfg_attr(docsrs, doc(cfg(feature = "dotenv")))]
+    #[cfg(feature = "dotenv")]
+    pub fn load_variables_dotenv<P: AsRef<Path>>(
+        &self,
+        path: P,
+        prefix: &str,
+    ) -> Result<(), Error> {
+        let (file, content) = read_file(path.as_ref())?;
+        self.load_dotenv(&file, &content, prefix)
+    }
+
+    /// Loads the variables of a `.env` string.
+    ///
+    /// See [`SrTemplate::load_variables_dotenv`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use srtemplate::SrTemplate;
+    ///
+    /// let ctx = SrTemplate::default();
+    /// ctx.load_variables_dotenv_str("# comment\nexport HOST=localhost\nPORT=\"8080\"", "env").unwrap();
+    ///
+    /// assert_eq!(ctx.render("{{ env.HOST }}:{{ env.PORT }}").unwrap(), "localhost:8080");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LoadVariables`] if the content has a malformed line.
+    #[cfg_attr(docsrs, doc(cfg(feature = "dotenv")))]
+    #[cfg(feature = "dotenv")]
+    pub fn load_variables_dotenv_str(&self, content: &str, prefix: &str) -> Result<(), Error> {
+        self.load_dotenv(STR_SOURCE, content, prefix)
+    }
+
+    #[cfg(feature = "dotenv")]
+    fn load_dotenv(&self, file: &str, content: &str, prefix: &str) -> Result<(), Error> {
+        let mut vars = Vec::new();
+
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(load_error(
+                    file,
+                    None,
+                    format!("line {}: expected KEY=VALUE", idx + 1),
+                ));
+            };
+            let key = key.trim();
+            if key.is_empty()
+                || !key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+            {
+                return Err(load_error(
+                    file,
+                    None,
+                    format!("line {}: invalid key \"{key}\"", idx + 1),
+                ));
+            }
+
+            // the key of the errors is the name of the variable, like for the other formats
+            let name = if prefix.is_empty() {
+                key.to_owned()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            let value = dotenv_value(value.trim()).map_err(|e| {
+                load_error(file, Some(name.clone()), format!("line {}: {e}", idx + 1))
+            })?;
+
+            vars.push((name, value));
+        }
+
+        vars.into_iter()
+            .for_each(|(name, value)| self.add_variable(name, value));
+        Ok(())
+    }
+}
+
+fn load_error(file: &str, key: Option<String>, message: impl ToString) -> Error {
+    Error::LoadVariables {
+        file: file.to_owned(),
+        key,
+        message: message.to_string(),
+    }
+}
+
+fn read_file(path: &Path) -> Result<(String, String), Error> {
+    let file = path.display().to_string();
+    let content = std::fs::read_to_string(path).map_err(|e| load_error(&file, None, e))?;
+
+    Ok((file, content))
+}
+
+#[cfg(feature = "toml")]
+fn toml_datetimes_to_string(value: &mut toml::Value) {
+    match value {
+        toml::Value::Datetime(datetime) => *value = toml::Value::String(datetime.to_string()),
+        toml::Value::Array(values) => values.iter_mut().for_each(toml_datetimes_to_string),
+        toml::Value::Table(table) => table
+            .iter_mut()
+            .for_each(|(_, value)| toml_datetimes_to_string(value)),
+        _ => {}
+    }
+}
+
+#[cfg(feature = "dotenv")]
+fn dotenv_value(value: &str) -> Result<String, &'static str> {
+    if let Some(value) = value.strip_prefix('"') {
+        let mut res = String::with_capacity(value.len());
+        let mut chars = value.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Ok(res),
+                '\\' => match chars.next() {
+                    Some('n') => res.push('\n'),
+                    Some('t') => res.push('\t'),
+                    Some('r') => res.push('\r'),
+                    Some(c) => res.push(c),
+                    None => break,
+                },
+                c => res.push(c),
+            }
+        }
+
+        return Err("unterminated double quoted value");
+    }
+
+    if let Some(value) = value.strip_prefix('\'') {
+        return value
+            .find('\'')
+            .map(|end| value[..end].to_owned())
+            .ok_or("unterminated single quoted value");
+    }
+
+    let value = value
+        .find(" #")
+        .map_or(value, |comment| &value[..comment])
+        .trim_end();
+
+    Ok(value.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, SrTemplate};
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_variables() {
+        let ctx = SrTemplate::default();
+        ctx.load_variables_json_str(
+            r#"{ "name": "srtemplate", "tags": ["fast", "tiny"], "meta": { "stars": 10, "fork": null } }"#,
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(
+            ctx.render("{{ name }} {{ tags.1 }} {{ meta.stars }}"),
+            Ok("srtemplate tiny 10".to_owned())
+        );
+        assert!(!ctx.contains_variable("meta.fork"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_invalid() {
+        let ctx = SrTemplate::default();
+        let res = ctx.load_variables_json_str(r#"{ "name": }"#, "cfg");
+
+        assert!(matches!(
+            res,
+            Err(Error::LoadVariables { file, key: None, .. }) if file == "<string>"
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_missing_file() {
+        let ctx = SrTemplate::default();
+        let res = ctx.load_variables_json("not_exists.json", "cfg");
+
+        assert!(matches!(
+            res,
+            Err(Error::LoadVariables { file, .. }) if file == "not_exists.json"
+        ));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_variables() {
+        let ctx = SrTemplate::default();
+        ctx.load_variables_toml_str(
+            "title = \"Release\"\n[package]\nversion = \"0.3.3\"\ndate = 2024-01-02\n",
+            "cfg",
+        )
+        .unwrap();
+
+        assert_eq!(
+            ctx.render("{{ cfg.title }} {{ cfg.package.version }} {{ cfg.package.date }}"),
+            Ok("Release 0.3.3 2024-01-02".to_owned())
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_variables() {
+        let ctx = SrTemplate::default();
+        ctx.load_variables_yaml_str("server:\n  hosts:\n    - a.com\n    - b.com\n", "")
+            .unwrap();
+
+        assert_eq!(
+            ctx.render("{{ server.hosts.0 }},{{ server.hosts.1 }}"),
+            Ok("a.com,b.com".to_owned())
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_invalid_key() {
+        let ctx = SrTemplate::default();
+        let res = ctx.load_variables_yaml_str("server:\n  [a, b]: value\n", "cfg");
+
+        assert!(matches!(
+            res,
+            Err(Error::LoadVariables { key: Some(key), .. }) if key == "cfg.server"
+        ));
+    }
+
+    #[cfg(feature = "dotenv")]
+    #[test]
+    fn dotenv_variables() {
+        let ctx = SrTemplate::default();
+        ctx.load_variables_dotenv_str(
+            "# database\nexport HOST=localhost # comment\nNAME='my #db'\nMOTD=\"hi\\n\\\"all\\\"\"\n",
+            "",
+        )
+        .unwrap();
+
+        assert_eq!(
+            ctx.render("{{ HOST }}|{{ NAME }}|{{ MOTD }}"),
+            Ok("localhost|my #db|hi\n\"all\"".to_owned())
+        );
+    }
+
+    #[cfg(feature = "dotenv")]
+    #[test]
+    fn dotenv_invalid() {
+        let ctx = SrTemplate::default();
+
+        assert!(matches!(
+            ctx.load_variables_dotenv_str("HOST=localhost\nINVALID", ""),
+            Err(Error::LoadVariables { key: None, message, .. }) if message.starts_with("line 2")
+        ));
+        assert!(matches!(
+            ctx.load_variables_dotenv_str("NAME=\"unterminated", "env"),
+            Err(Error::LoadVariables { key: Some(key), .. }) if key == "env.NAME"
+        ));
+    }
+
+    #[test]
+    fn error_keys() {
+        let ctx = SrTemplate::default();
+        let key = |res: Result<(), Error>| match res {
+            Err(Error::LoadVariables { key, .. }) => key,
+            res => panic!("expected LoadVariables, found {res:?}"),
+        };
+
+        #[cfg(feature = "json")]
+        assert_eq!(key(ctx.load_variables_json_str("[1,", "cfg")), None);
+        #[cfg(feature = "toml")]
+        assert_eq!(key(ctx.load_variables_toml_str("a = ", "cfg")), None);
+        #[cfg(feature = "yaml")]
+        {
+            let res = ctx.load_variables_yaml_str("server:\n  [a, b]: value\n", "cfg");
+            assert_eq!(key(res).as_deref(), Some("cfg.server"));
+            let res = ctx.load_variables_yaml_str("server:\n  [a, b]: value\n", "");
+            assert_eq!(key(res).as_deref(), Some("server"));
+        }
+        #[cfg(feature = "dotenv")]
+        {
+            let res = ctx.load_variables_dotenv_str("NAME=\"unterminated", "cfg");
+            assert_eq!(key(res).as_deref(), Some("cfg.NAME"));
+            let res = ctx.load_variables_dotenv_str("NAME=\"unterminated", "");
+            assert_eq!(key(res).as_deref(), Some("NAME"));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::ops::Range;
+
+use crate::ast::{walk_function, Span, TemplateNode, Visitor};
+use crate::error::Location;
+
+/// Kinds of names that can be referenced from a template
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ReferenceKind {
+    /// A variable, like `{{ user.name }}`
+    Variable,
+    /// A function call, like `{{ toLower(name) }}`
+    Function,
+}
+
+/// A variable or function referenced from a template, with its location in the template
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reference<'t> {
+    /// Whether the name is a variable or a function
+    pub kind: ReferenceKind,
+    /// The referenced name
+    pub name: &'t str,
+    /// Byte range of the name in the template
+    pub span: Range<usize>,
+    /// Line of the name, starting at 1
+    pub line: usize,
+    /// Column of the name, starting at 1
+    pub column: usize,
+    /// Number of arguments of a function call, `None` for variables
+    pub args: Option<usize>,
+}
+
+impl<'t> Reference<'t> {
+    fn new(
+        input: &'t str,
+        kind: ReferenceKind,
+        name: &'t str,
+        span: &Span,
+        args: Option<usize>,
+    ) -> Self {
+        let span = span.start..span.end.min(span.start + name.len());
+        let Location { line, column, .. } = Location::new(input, span.clone());
+
+        Self {
+            kind,
+            name,
+            span,
+            line,
+            column,
+            args,
+        }
+    }
+}
+
+/// Collects the references of the nodes in the order they appear, including function arguments
+pub fn collect<'t>(input: &'t str, nodes: &[TemplateNode<'t>], refs: &mut Vec<Reference<'t>>) {
+    Collector { input, refs }.visit_nodes(nodes);
+}
+
+struct Collector<'t, 'r> {
+    input: &'t str,
+    refs: &'r mut Vec<Reference<'t>>,
+}
+
+impl<'t> Visitor<'t> for Collector<'t, '_> {
+    fn visit_variable(&mut self, name: &'t str, span: &Span) {
+        self.refs.push(Reference::new(
+            self.input,
+            ReferenceKind::Variable,
+            name,
+            span,
+            None,
+        ));
+    }
+
+    fn visit_function(&mut self, name: &'t str, args: &[TemplateNode<'t>], span: &Span) {
+        self.refs.push(Reference::new(
+            self.input,
+            ReferenceKind::Function,
+            name,
+            span,
+            Some(args.len()),
+        ));
+        walk_function(self, args);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ReferenceKind, SrTemplate};
+
+    #[test]
+    fn nested_references() {
+        let ctx = SrTemplate::default();
+        let template = "Hi {{ user.name }}\n{{ toLower(trim(user.name, \"x\"), 5) }}";
+        let refs = ctx.references(template).unwrap();
+
+        let refs = refs
+            .iter()
+            .map(|r| (r.kind, r.name, r.span.clone(), r.line, r.column))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            refs,
+            [
+                (ReferenceKind::Variable, "user.name", 6..15, 1, 7),
+                (ReferenceKind::Function, "toLower", 22..29, 2, 4),
+                (ReferenceKind::Function, "trim", 30..34, 2, 12),
+                (ReferenceKind::Variable, "user.name", 35..44, 2, 17),
+            ]
+        );
+        assert_eq!(&template[refs[2].2.clone()], "trim");
+        assert_eq!(
+            ctx.references(template)
+                .unwrap()
+                .iter()
+                .map(|r| r.args)
+                .collect::<Vec<_>>(),
+            [None, Some(2), Some(2), None]
+        );
+    }
+
+    #[test]
+    fn referenced_names() {
+        let ctx = SrTemplate::default();
+        let template = "{{ b }} {{ a }} {{ toUpper(b) }} {{ toUpper(a) }}";
+
+        assert_eq!(
+            ctx.referenced_variables(template)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+        assert_eq!(
+            ctx.referenced_functions(template)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            ["toUpper"]
+        );
+    }
+
+    #[test]
+    fn invalid_template() {
+        let ctx = SrTemplate::default();
+
+        assert!(ctx.references("{{ toLower(a }}").is_err());
+    }
+}
@@ -0,0 +1,490 @@
+use std::fmt::Display;
+
+use serde::ser::{self, Serialize};
+
+use crate::error::Error;
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Serialize(msg.to_string())
+    }
+}
+
+/// Walks any [`Serialize`] value and collects it as a list of dotted variables.
+///
+/// Scalars are stored under the current path, structs and maps append their field or key,
+/// sequences and tuples append the index and enum variants append the variant name.
+/// `None` and unit values are omitted.
+///
+/// # Arguments
+///
+/// * `name`: The prefix for all collected variables, an empty prefix produces top-level names
+/// * `value`: The value to be walked
+///
+/// # Errors
+///
+/// Returns [`Error::Serialize`] if a map key cannot be represented as a string
+/// or the value's `Serialize` implementation fails.
+pub fn to_variables<T: Serialize + ?Sized>(
+    name: &str,
+    value: &T,
+) -> Result<Vec<(String, String)>, Error> {
+    collect(name, value).map_err(|(message, _)| Error::Serialize(message))
+}
+
+/// Same as [`to_variables`], but on failure returns the error message
+/// together with the path of the value that failed.
+pub fn collect<T: Serialize + ?Sized>(
+    name: &str,
+    value: &T,
+) -> Result<Vec<(String, String)>, (String, String)> {
+    let mut collector = Collector {
+        path: name.to_owned(),
+        vars: Vec::new(),
+    };
+
+    match value.serialize(&mut collector) {
+        Ok(()) => Ok(collector.vars),
+        Err(Error::Serialize(message)) => Err((message, collector.path)),
+        Err(e) => Err((e.to_string(), collector.path)),
+    }
+}
+
+struct Collector {
+    path: String,
+    vars: Vec<(String, String)>,
+}
+
+impl Collector {
+    fn push_segment(&mut self, segment: &str) -> usize {
+        let len = self.path.len();
+        if !self.path.is_empty() {
+            self.path.push('.');
+        }
+        self.path.push_str(segment);
+        len
+    }
+
+    fn pop_segment(&mut self, len: usize) {
+        self.path.truncate(len);
+    }
+
+    fn insert<T: ToString + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.vars.push((self.path.clone(), value.to_string()));
+        Ok(())
+    }
+
+    /// On failure the segment is kept, so the path points to the value that failed
+    fn nested<T: Serialize + ?Sized>(&mut self, segment: &str, value: &T) -> Result<(), Error> {
+        let len = self.push_segment(segment);
+        value.serialize(&mut *self)?;
+        self.pop_segment(len);
+        Ok(())
+    }
+}
+
+/// Serializes a map key, which must result in a single scalar value
+fn key_to_string<T: Serialize + ?Sized>(key: &T) -> Result<String, Error> {
+    let mut vars = to_variables("", key)?;
+
+    match vars.pop() {
+        Some((path, key)) if path.is_empty() && vars.is_empty() => Ok(key),
+        _ => Err(Error::Serialize(
+            "map keys must be strings or scalars".to_owned(),
+        )),
+    }
+}
+
+/// Shared state for every compound value (sequences, maps, structs and variants)
+struct Compound<'c> {
+    ser: &'c mut Collector,
+    index: usize,
+    key: Option<String>,
+    variant_len: Option<usize>,
+}
+
+impl<'c> Compound<'c> {
+    fn new(ser: &'c mut Collector, variant: Option<&str>) -> Self {
+        let variant_len = variant.map(|v| ser.push_segment(v));
+        Self {
+            ser,
+            index: 0,
+            key: None,
+            variant_len,
+        }
+    }
+
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let index = self.index.to_string();
+        self.index += 1;
+        self.ser.nested(&index, value)
+    }
+
+    fn finish(self) -> Result<(), Error> {
+        if let Some(len) = self.variant_len {
+            self.ser.pop_segment(len);
+        }
+        Ok(())
+    }
+}
+
+macro_rules! serialize_display {
+    ($($method: ident: $t: ty),* $(,)?) => {
+        $(
+            fn $method(self, v: $t) -> Result<(), Error> {
+                self.insert(&v)
+            }
+        )*
+    };
+}
+
+impl<'c> ser::Serializer for &'c mut Collector {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'c>;
+    type SerializeTuple = Compound<'c>;
+    type SerializeTupleStruct = Compound<'c>;
+    type SerializeTupleVariant = Compound<'c>;
+    type SerializeMap = Compound<'c>;
+    type SerializeStruct = Compound<'c>;
+    type SerializeStructVariant = Compound<'c>;
+
+    serialize_display! {
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.insert(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.insert(&String::from_utf8_lossy(v))
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.insert(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.nested(variant, value)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Ok(Compound::new(self, Some(variant)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Ok(Compound::new(self, Some(variant)))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key_to_string(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::Serialize("map value serialized before its key".to_owned()))?;
+        self.ser.nested(&key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.ser.nested(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.ser.nested(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Serialize;
+
+    use super::{collect, to_variables};
+
+    #[derive(Serialize)]
+    enum Status {
+        Pending,
+        Shipped { carrier: String },
+        Cancelled(String),
+    }
+
+    #[derive(Serialize)]
+    struct Item {
+        name: &'static str,
+        qty: u8,
+    }
+
+    #[derive(Serialize)]
+    struct Order {
+        id: u32,
+        note: Option<&'static str>,
+        coupon: Option<&'static str>,
+        items: Vec<Item>,
+        meta: BTreeMap<&'static str, f32>,
+        status: Status,
+        previous: (Status, Status),
+    }
+
+    #[test]
+    fn nested_values() {
+        let order = Order {
+            id: 7,
+            note: Some("fragile"),
+            coupon: None,
+            items: vec![
+                Item {
+                    name: "book",
+                    qty: 2,
+                },
+                Item {
+                    name: "pen",
+                    qty: 10,
+                },
+            ],
+            meta: BTreeMap::from_iter([("weight", 1.5)]),
+            status: Status::Shipped {
+                carrier: "dhl".to_owned(),
+            },
+            previous: (Status::Pending, Status::Cancelled("stock".to_owned())),
+        };
+
+        let vars = to_variables("order", &order).unwrap();
+        let vars = vars
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vars,
+            [
+                ("order.id", "7"),
+                ("order.note", "fragile"),
+                ("order.items.0.name", "book"),
+                ("order.items.0.qty", "2"),
+                ("order.items.1.name", "pen"),
+                ("order.items.1.qty", "10"),
+                ("order.meta.weight", "1.5"),
+                ("order.status.Shipped.carrier", "dhl"),
+                ("order.previous.0", "Pending"),
+                ("order.previous.1.Cancelled", "stock"),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_prefix() {
+        let vars = to_variables(
+            "",
+            &Item {
+                name: "book",
+                qty: 2,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            vars,
+            [
+                ("name".to_owned(), "book".to_owned()),
+                ("qty".to_owned(), "2".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_map_key() {
+        let map = BTreeMap::from_iter([(vec![1u8], "value")]);
+
+        assert!(to_variables("map", &map).is_err());
+    }
+
+    #[test]
+    fn failed_path() {
+        let value = BTreeMap::from_iter([("inner", BTreeMap::from_iter([((1, 2), "value")]))]);
+        let (_, path) = collect("root", &value).unwrap_err();
+
+        assert_eq!(path, "root.inner");
+    }
+}
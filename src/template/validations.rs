@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use crate::prelude::FunctionError;
+use crate::template::function::ParseArgumentError;
 
 pub type ValidationResult = Result<(), FunctionError>;
 
@@ -104,9 +105,53 @@ pub fn arg_type<T: FromStr>(arg: String) -> ValidationResult {
     Ok(())
 }
 
+/// Parses an argument of a function into `T`, the error names the function, the parameter,
+/// the argument and the expected type.
+///
+/// # Arguments
+///
+/// * `arg` - The argument to be parsed.
+/// * `function` - Name of the function receiving the argument.
+/// * `param` - Name of the parameter of the argument.
+/// * `ty` - Name of the type `T`, shown in the error.
+///
+/// # Example
+///
+/// ```
+/// use srtemplate::prelude::validations::parse_argument;
+///
+/// assert_eq!(parse_argument::<u8>("42", "repeat", "times", "u8"), Ok(42));
+/// assert_eq!(
+///     parse_argument::<u8>("many", "repeat", "times", "u8")
+///         .unwrap_err()
+///         .to_string(),
+///     "Argument `times` of `repeat` must be u8, but found \"many\""
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns [`FunctionError::ParseArgument`] if the argument cannot be parsed into `T`.
+pub fn parse_argument<T: FromStr>(
+    arg: &str,
+    function: &str,
+    param: &str,
+    ty: &str,
+) -> Result<T, FunctionError> {
+    arg.parse::<T>().map_err(|_| {
+        FunctionError::ParseArgument(Box::new(ParseArgumentError {
+            function: function.to_owned(),
+            param: param.to_owned(),
+            value: arg.to_owned(),
+            ty: ty.to_owned(),
+        }))
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::prelude::validations::{arg_type, args_max_len, args_min_len};
+    use crate::prelude::validations::{arg_type, args_max_len, args_min_len, parse_argument};
+    use crate::template::function::ParseArgumentError;
 
     #[test]
     fn test_arg_min_len() {
@@ -144,4 +189,23 @@ mod tests {
         assert!(arg_type::<u32>("3.5".to_string()).is_err());
         assert!(arg_type::<u32>("-54".to_string()).is_err());
     }
+
+    #[test]
+    fn test_parse_argument() {
+        assert_eq!(
+            parse_argument::<f32>("1.5", "scale", "factor", "f32"),
+            Ok(1.5)
+        );
+        assert_eq!(
+            parse_argument::<f32>("x", "scale", "factor", "f32"),
+            Err(crate::prelude::FunctionError::ParseArgument(Box::new(
+                ParseArgumentError {
+                    function: "scale".to_owned(),
+                    param: "factor".to_owned(),
+                    value: "x".to_owned(),
+                    ty: "f32".to_owned(),
+                }
+            )))
+        );
+    }
 }
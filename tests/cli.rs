@@ -0,0 +1,147 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Writes `content` to a file of the temporary directory, unique for each test
+fn file(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("srtemplate-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// Runs the binary with `args`, writing `stdin` to its standard input
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_srtemplate"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn render_from_stdin() {
+    let output = run(
+        &["--no-env", "--var", "name=World"],
+        "Hello {{ toUpper(name) }}!",
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Hello WORLD!");
+}
+
+#[test]
+fn render_with_variable_files() {
+    let template = file("render.txt", "<% user.name %> is <% age %>");
+    let vars = file("render.json", r#"{ "user": { "name": "Ana" }, "age": 30 }"#);
+    let output = run(
+        &[
+            template.to_str().unwrap(),
+            "--no-env",
+            "--vars",
+            vars.to_str().unwrap(),
+            "--var",
+            "age=31",
+            "--start",
+            "<%",
+            "--close",
+            "%>",
+        ],
+        "",
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Ana is 31");
+}
+
+#[test]
+fn render_missing_variable() {
+    let output = run(&["--no-env"], "Hello {{ name }}");
+
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("Variable not found: name"));
+}
+
+#[test]
+fn check_valid_templates() {
+    let template = file("valid.txt", "{{ toLower(name) }} {{ other }}");
+    let output = run(&["check", template.to_str().unwrap()], "");
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).is_empty());
+}
+
+#[test]
+fn check_lists_every_error() {
+    let template = file(
+        "invalid.txt",
+        "{{ trim( }}\n{{ upper(name) }} {{ toLower(x) }}\n{{ 1x( }}",
+    );
+    let path = template.to_str().unwrap();
+    let output = run(&["check", path], "");
+
+    assert_eq!(output.status.code(), Some(1));
+    let lines = stderr(&output)
+        .lines()
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4, "{lines:?}");
+    assert!(
+        lines[0].starts_with(&format!("{path}:1:9: ")),
+        "{}",
+        lines[0]
+    );
+    assert_eq!(
+        lines[1],
+        format!("{path}:2:4: Function not implemented: upper")
+    );
+    assert!(
+        lines[2].starts_with(&format!("{path}:3:7: ")),
+        "{}",
+        lines[2]
+    );
+    assert_eq!(lines[3], "Found 3 error(s) in 1 file(s)");
+}
+
+#[test]
+fn check_and_render_agree_on_locations() {
+    let template = file("location.txt", "x\n{{ name ");
+    let path = template.to_str().unwrap();
+
+    let check = stderr(&run(&["check", path], ""));
+    let render = stderr(&run(&[path, "--no-env"], ""));
+
+    assert!(check.starts_with(&format!("{path}:2:8: ")), "{check}");
+    assert!(render.contains("--> 2:8"), "{render}");
+}
+
+#[test]
+fn vars_of_template() {
+    let template = file("vars.txt", "{{ b }} {{ toUpper(a) }} {{ a }}");
+    let output = run(&["vars", template.to_str().unwrap()], "");
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "variable a\nvariable b\nfunction toUpper\n"
+    );
+}
@@ -0,0 +1,5 @@
+#[test]
+fn generics() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/variable/*.rs");
+}
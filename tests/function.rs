@@ -0,0 +1,244 @@
+use std::fmt::Display;
+
+use srtemplate::prelude::{FunctionError, FunctionSignature, ParseArgumentError};
+use srtemplate::{function, Error, SrTemplate};
+
+/// Pads a text with `fill`
+/// up to `width`
+#[function]
+fn pad(mut text: String, width: Option<usize>, #[default = "-"] fill: char) {
+    let width = width.unwrap_or(text.len() + 2);
+    while text.len() < width {
+        text.push(fill);
+    }
+    Ok(text)
+}
+
+#[function]
+fn scale(#[default = 2] factor: u8, values: Vec<u8>) {
+    let values = values
+        .iter()
+        .map(|value| (value * factor).to_string())
+        .collect::<Vec<_>>();
+    Ok(values.join(","))
+}
+
+#[function]
+fn join(separator: String, parts: &[String]) {
+    Ok(parts.join(&separator))
+}
+
+#[function(args)]
+fn shout(times: u8) {
+    Ok(format!("{}{}", args[0], "!".repeat(times as usize)))
+}
+
+#[function]
+pub fn sum(values: Vec<i32>) -> i32 {
+    values.iter().sum()
+}
+
+#[function]
+fn greet(name: String) -> String {
+    format!("Hi {name}")
+}
+
+#[function]
+fn double(value: i64) -> impl Display {
+    value * 2
+}
+
+#[function]
+fn divide(a: f64, b: f64) -> Result<f64, String> {
+    if b == 0.0 {
+        return Err("division by zero".to_owned());
+    }
+    Ok(a / b)
+}
+
+#[function]
+fn checked(value: u8) -> Result<String, FunctionError> {
+    let value = value
+        .checked_mul(10)
+        .ok_or_else(|| FunctionError::InvalidArgument(value.to_string()))?;
+    Ok(value.to_string())
+}
+
+#[function]
+fn first(values: Vec<char>) -> Option<char> {
+    values.first().copied()
+}
+
+#[function]
+fn count(text: String, separator: Option<char>) {
+    let args = text.split(separator.unwrap_or(',')).collect::<Vec<_>>();
+    Ok(args.len().to_string())
+}
+
+fn context() -> SrTemplate<'static> {
+    let ctx = SrTemplate::default();
+    ctx.add_function("pad", pad);
+    ctx.add_function("scale", scale);
+    ctx.add_function("join", join);
+    ctx.add_function("shout", shout);
+    ctx.add_function("count", count);
+    ctx.add_function("greet", greet);
+    ctx.add_function("double", double);
+    ctx.add_function("divide", divide);
+    ctx.add_function("checked", checked);
+    ctx.add_function("first", first);
+    ctx
+}
+
+#[test]
+fn optional_params() {
+    let ctx = context();
+
+    assert_eq!(ctx.render(r#"{{ pad("ab") }}"#).unwrap(), "ab--");
+    assert_eq!(ctx.render(r#"{{ pad("ab", "3", "*") }}"#).unwrap(), "ab*");
+    assert!(ctx.render(r#"{{ pad() }}"#).is_err());
+    assert!(ctx.render(r#"{{ pad("a", "1", "*", "x") }}"#).is_err());
+    assert!(ctx.render(r#"{{ pad("ab", "x") }}"#).is_err());
+}
+
+#[test]
+fn variadic_params() {
+    let ctx = context();
+
+    assert_eq!(ctx.render(r#"{{ scale() }}"#).unwrap(), "");
+    assert_eq!(ctx.render(r#"{{ scale("3", "1", "2") }}"#).unwrap(), "3,6");
+    assert!(ctx.render(r#"{{ scale("3", "1", "x") }}"#).is_err());
+
+    assert_eq!(ctx.render(r#"{{ join("-", "a", "b") }}"#).unwrap(), "a-b");
+    assert_eq!(ctx.render(r#"{{ join("-") }}"#).unwrap(), "");
+}
+
+#[test]
+fn body_reading_args() {
+    let ctx = context();
+
+    assert_eq!(ctx.render("{{ shout(3) }}").unwrap(), "3!!!");
+}
+
+#[test]
+fn body_with_local_args() {
+    let ctx = context();
+
+    assert_eq!(ctx.render(r#"{{ count("a,b,c") }}"#).unwrap(), "3");
+    assert_eq!(ctx.render(r#"{{ count("a b", " ") }}"#).unwrap(), "2");
+}
+
+#[test]
+fn return_types() {
+    let ctx = context();
+
+    assert_eq!(ctx.render(r#"{{ greet("Ana") }}"#).unwrap(), "Hi Ana");
+    assert_eq!(ctx.render("{{ double(21) }}").unwrap(), "42");
+    assert_eq!(ctx.render("{{ divide(1, 4) }}").unwrap(), "0.25");
+    assert_eq!(ctx.render("{{ checked(2) }}").unwrap(), "20");
+    assert_eq!(ctx.render(r#"{{ first("a", "b") }}"#).unwrap(), "a");
+    assert_eq!(ctx.render("{{ first() }}").unwrap(), "");
+}
+
+#[test]
+fn returned_errors() {
+    let ctx = context();
+
+    let Err(Error::Function(error, location)) = ctx.render("{{ divide(1, 0) }}") else {
+        panic!("expected a function error");
+    };
+    assert_eq!(
+        error,
+        FunctionError::RuntimeError("division by zero".to_owned())
+    );
+    assert_eq!(
+        error.to_string(),
+        "Error calling the function: division by zero"
+    );
+    assert_eq!((location.line, location.column), (1, 4));
+
+    let Err(Error::Function(error, _)) = ctx.render("{{ checked(30) }}") else {
+        panic!("expected a function error");
+    };
+    assert_eq!(error, FunctionError::InvalidArgument("30".to_owned()));
+}
+
+#[test]
+fn argument_parse_errors() {
+    let ctx = context();
+
+    let Err(Error::Function(error, _)) = ctx.render(r#"{{ double("two") }}"#) else {
+        panic!("expected a function error");
+    };
+    assert_eq!(
+        error.to_string(),
+        "Argument `value` of `double` must be i64, but found \"two\""
+    );
+
+    let Err(Error::Function(error, _)) = ctx.render(r#"{{ first("a", "bc") }}"#) else {
+        panic!("expected a function error");
+    };
+    assert_eq!(
+        error,
+        FunctionError::ParseArgument(Box::new(ParseArgumentError {
+            function: "first".to_owned(),
+            param: "values".to_owned(),
+            value: "bc".to_owned(),
+            ty: "char".to_owned(),
+        }))
+    );
+}
+
+#[test]
+fn signatures() {
+    assert_eq!(
+        pad_signature(),
+        FunctionSignature::new("pad")
+            .param("text", "String")
+            .optional_param("width", "usize")
+            .optional_param("fill", "char")
+            .description("Pads a text with `fill`\nup to `width`")
+    );
+    assert_eq!(sum_signature().to_string(), "sum(...values: i32)");
+    assert_eq!(sum_signature().description, None);
+}
+
+#[test]
+fn register_with_signature() {
+    let ctx = SrTemplate::default();
+    ctx.clear_functions();
+    register_pad(&ctx);
+    register_sum(&ctx);
+    ctx.add_function("greet", greet);
+
+    let names = ctx
+        .functions()
+        .into_iter()
+        .map(|(name, signature)| (name.into_owned(), signature.is_some()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("greet".to_owned(), false),
+            ("pad".to_owned(), true),
+            ("sum".to_owned(), true),
+        ]
+    );
+    assert_eq!(ctx.signature("pad"), Some(pad_signature()));
+
+    assert_eq!(ctx.validate(r#"{{ pad("a") }} {{ sum() }}"#), Ok(()));
+    let errors = ctx
+        .validate(r#"{{ pad("a", "1", "*", "x") }}"#)
+        .unwrap_err();
+    assert!(matches!(
+        errors.as_slice(),
+        [Error::Function(FunctionError::ArgumentsIncomplete(4, 3), _)]
+    ));
+    assert_eq!(ctx.render(r#"{{ pad("a") }}"#).unwrap(), "a--");
+}
+
+#[test]
+fn compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/function/fail/*.rs");
+}
@@ -0,0 +1,42 @@
+use std::fmt::Write;
+
+use srtemplate::{Error, SrTemplate, Template};
+
+#[derive(Template)]
+#[template(source = "Hi {{ toUpper(name) }}")]
+struct Greeting {
+    name: String,
+}
+
+#[derive(Template)]
+#[template(source = "Hi {{ shout(name) }}")]
+struct Shout {
+    name: String,
+}
+
+#[test]
+fn display_with_builtin_functions() {
+    let greeting = Greeting {
+        name: "Ana".to_owned(),
+    };
+
+    assert_eq!(greeting.to_string(), "Hi ANA");
+    assert!(std::ptr::eq(
+        srtemplate::compiled::default_context(),
+        srtemplate::compiled::default_context()
+    ));
+}
+
+#[test]
+fn display_failure_keeps_render_error() {
+    let shout = Shout {
+        name: "Ana".to_owned(),
+    };
+
+    let mut output = String::new();
+    assert!(write!(output, "{shout}").is_err());
+    assert!(matches!(
+        shout.render(&SrTemplate::default()),
+        Err(Error::FunctionNotImplemented(name, _)) if name == "shout"
+    ));
+}
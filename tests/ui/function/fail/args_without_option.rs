@@ -0,0 +1,8 @@
+use srtemplate::function;
+
+#[function]
+fn shout(times: u8) {
+    Ok(format!("{}{}", args[0], "!".repeat(times as usize)))
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use srtemplate::function;
+
+#[function]
+fn repeat(text: String, #[default] times: usize) {
+    Ok(text.repeat(times))
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use srtemplate::function;
+
+#[function]
+fn pad(width: Option<usize>, text: String) {
+    Ok(text)
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use srtemplate::function;
+
+struct Greeter;
+
+impl Greeter {
+    #[function]
+    fn greet(&self, name: String) {
+        Ok(name)
+    }
+}
+
+fn main() {}
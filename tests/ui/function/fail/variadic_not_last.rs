@@ -0,0 +1,8 @@
+use srtemplate::function;
+
+#[function]
+fn join(parts: &[String], separator: String) {
+    Ok(parts.join(&separator))
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use srtemplate::{SrTemplate, Variable};
+
+#[derive(Variable)]
+struct Grid<const N: usize, T: Copy = u8>
+where
+    T: std::fmt::Display,
+{
+    #[template(with = "first")]
+    cells: [T; N],
+    size: usize,
+}
+
+fn first<T: Copy + ToString, const N: usize>(cells: &[T; N]) -> String {
+    cells.first().map(T::to_string).unwrap_or_default()
+}
+
+fn main() {
+    let ctx = SrTemplate::default();
+    ctx.add(Grid {
+        cells: [3u8, 4],
+        size: 2,
+    });
+
+    assert_eq!(
+        ctx.render("{{ Grid.cells }} {{ Grid.size }}").unwrap(),
+        "3 2"
+    );
+}
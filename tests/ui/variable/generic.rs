@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use srtemplate::{SrTemplate, Variable};
+
+#[derive(Variable)]
+struct Pair<A, B: Display>
+where
+    A: Clone,
+{
+    left: A,
+    right: Option<B>,
+    scores: BTreeMap<A, u8>,
+}
+
+#[derive(Variable)]
+struct Wrapper<T, U> {
+    #[template(nested)]
+    inner: T,
+    items: Vec<U>,
+}
+
+#[derive(Variable)]
+#[template(rename = "address")]
+struct Address {
+    city: String,
+}
+
+#[derive(Variable)]
+enum Either<L, R> {
+    Left(L),
+    Right { value: R },
+}
+
+fn main() {
+    let ctx = SrTemplate::default();
+    ctx.add(Pair {
+        left: "a".to_string(),
+        right: Some(2),
+        scores: BTreeMap::from([("a".to_string(), 1)]),
+    });
+    ctx.add(Wrapper {
+        inner: Address {
+            city: "Lima".to_string(),
+        },
+        items: vec!["x"],
+    });
+    ctx.add(Either::<u8, &str>::Right { value: "r" });
+
+    assert_eq!(
+        ctx.render("{{ Pair.left }} {{ Pair.right }} {{ Pair.scores.a }}")
+            .unwrap(),
+        "a 2 1"
+    );
+    assert_eq!(
+        ctx.render("{{ Wrapper.inner.city }} {{ Wrapper.items.0 }}")
+            .unwrap(),
+        "Lima x"
+    );
+    assert_eq!(
+        ctx.render("{{ Either }} {{ Either.value }}").unwrap(),
+        "right r"
+    );
+}
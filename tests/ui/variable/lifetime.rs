@@ -0,0 +1,38 @@
+use srtemplate::{SrTemplate, Variable};
+
+#[derive(Variable)]
+struct Message<'a, 'b: 'a> {
+    sender: &'a str,
+    tags: Vec<&'b str>,
+}
+
+#[derive(Variable)]
+struct Reply<'a, T: 'a> {
+    #[template(nested)]
+    message: &'a T,
+    body: std::borrow::Cow<'a, str>,
+}
+
+fn main() {
+    let text = String::from("hello");
+    let message = Message {
+        sender: "ana",
+        tags: vec!["urgent", text.as_str()],
+    };
+    let reply = Reply {
+        message: &message,
+        body: "ok".into(),
+    };
+
+    let ctx = SrTemplate::default();
+    ctx.add(&message);
+    ctx.add(&reply);
+
+    assert_eq!(
+        ctx.render(
+            "{{ Message.sender }} {{ Message.tags.1 }} {{ Reply.message.sender }} {{ Reply.body }}"
+        )
+        .unwrap(),
+        "ana hello ana ok"
+    );
+}